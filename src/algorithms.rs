@@ -0,0 +1,180 @@
+//General purpose algorithms that don't belong to any one day
+//
+//DisjointSet answers "are these two things connected?" as connections are added, and
+//label_components uses it to split a grid into connected areas without any recursion.
+
+//Union-find over the elements 0..len, with path compression and union by rank
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    sets: usize,
+}
+
+impl DisjointSet {
+    //Every element starts in a set of its own
+    pub fn new(len: usize) -> DisjointSet {
+        DisjointSet {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            sets: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    //Number of separate sets left
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    //The representative of the set holding element, every element on the way is pointed straight at it
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut current = element;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    //Merge the sets holding a and b, returns false if they were already the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        //Hang the shallower tree under the deeper one
+        let (root, child) = if self.ranks[a] < self.ranks[b] { (b, a) } else { (a, b) };
+        if self.ranks[root] == self.ranks[child] {
+            self.ranks[root] += 1;
+        }
+        self.parents[child] = root;
+        self.sizes[root] += self.sizes[child];
+        self.sets -= 1;
+
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    //Number of elements in the set holding element
+    pub fn size_of(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+}
+
+//Labels the connected areas of a grid, where neighbours (up, down, left and right) are joined when same says so.
+//Returns a grid of labels the same shape as the input, and the number of labels.
+//Labels count up from 0 in the order their areas are first seen reading row by row.
+pub fn label_components<T>(grid: &[Vec<T>], same: impl Fn(&T, &T) -> bool) -> (Vec<Vec<usize>>, usize) {
+    let width = grid.first().map_or(0, |row| row.len());
+    let index = |x: usize, y: usize| y * width + x;
+
+    let mut set = DisjointSet::new(width * grid.len());
+
+    //Joining each cell to the ones right and below covers every pair of neighbours once
+    for (y, row) in grid.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            if x + 1 < row.len() && same(cell, &row[x + 1]) {
+                set.union(index(x, y), index(x + 1, y));
+            }
+            if let Some(below) = grid.get(y + 1).and_then(|next| next.get(x)) {
+                if same(cell, below) {
+                    set.union(index(x, y), index(x, y + 1));
+                }
+            }
+        }
+    }
+
+    //Swap each set's representative for a label numbered in reading order
+    let mut labels_by_root = vec![None; set.len()];
+    let mut count = 0;
+    let labels = grid.iter()
+        .enumerate()
+        .map(|(y, row)| (0..row.len())
+            .map(|x| {
+                let root = set.find(index(x, y));
+                *labels_by_root[root].get_or_insert_with(|| {
+                    count += 1;
+                    count - 1
+                })
+            })
+            .collect())
+        .collect();
+
+    (labels, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disjoint_set_unions() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.sets(), 6);
+
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+
+        assert!(set.connected(0, 3));
+        assert!(!set.connected(0, 4));
+        assert_eq!(set.size_of(2), 4);
+        assert_eq!(set.size_of(5), 1);
+        assert_eq!(set.sets(), 3);
+    }
+
+    #[test]
+    fn long_chains_are_flattened() {
+        let mut set = DisjointSet::new(100_000);
+        for i in 1..set.len() {
+            set.union(i - 1, i);
+        }
+
+        assert_eq!(set.sets(), 1);
+        assert!(set.connected(0, 99_999));
+    }
+
+    #[test]
+    fn label_grid_components() {
+        let grid: Vec<Vec<char>> = ["AAB", "ABB", "CAA"].iter().map(|row| row.chars().collect()).collect();
+
+        let (labels, count) = label_components(&grid, |a, b| a == b);
+
+        //The A in the bottom row isn't joined to the other As, diagonals don't count
+        assert_eq!(count, 4);
+        assert_eq!(labels, vec![vec![0, 0, 1], vec![0, 1, 1], vec![2, 3, 3]]);
+    }
+
+    #[test]
+    fn large_regions_dont_recurse() {
+        let grid = vec![vec![1; 1000]; 1000];
+
+        let (labels, count) = label_components(&grid, |a, b| a == b);
+
+        assert_eq!(count, 1);
+        assert!(labels.iter().flatten().all(|&label| label == 0));
+    }
+}
@@ -0,0 +1,160 @@
+//Confirmed puzzle answers, kept in answers.toml at the root of the repo
+//
+//The file is a small subset of TOML, one table per day and one key per part:
+//
+//  [day18]
+//  part1 = 302
+//  part2 = "24,32"
+
+use std::{collections::BTreeMap, path::{Path, PathBuf}};
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::solver::Answer;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+//Recorded answers keyed by (day, part)
+pub type Answers = BTreeMap<(u8, u8), Answer>;
+
+//The answers file where we're running from, otherwise the one alongside the sources
+pub fn default_answers_file() -> PathBuf {
+    let local = Path::new(ANSWERS_FILE);
+    if local.is_file() {
+        local.to_path_buf()
+    } else {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(ANSWERS_FILE)
+    }
+}
+
+pub fn load_answers(path: &Path) -> anyhow::Result<Answers> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("Failed to read answers {}", path.display()))?;
+
+    parse_answers(&text).with_context(|| format!("Invalid answers file {}", path.display()))
+}
+
+pub fn parse_answers(text: &str) -> anyhow::Result<Answers> {
+    let mut answers = Answers::new();
+    let mut day: Option<u8> = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let value = table.trim().strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| anyhow!("Line {line_number}: expected a [dayN] table, got '{line}'"))?;
+            day = Some(value);
+            continue;
+        }
+
+        let (key, value) = line.split_once('=')
+            .ok_or_else(|| anyhow!("Line {line_number}: expected 'partN = answer', got '{line}'"))?;
+        let day = day.ok_or_else(|| anyhow!("Line {line_number}: answer given before any [dayN] table"))?;
+        let part: u8 = match key.trim() {
+            "part1" => 1,
+            "part2" => 2,
+            other => bail!("Line {line_number}: unknown key '{other}', expected part1 or part2"),
+        };
+        let answer = parse_answer(value.trim()).with_context(|| format!("Line {line_number}"))?;
+
+        if answers.insert((day, part), answer).is_some() {
+            bail!("Line {line_number}: day {day} part {part} is recorded more than once");
+        }
+    }
+
+    Ok(answers)
+}
+
+fn parse_answer(value: &str) -> anyhow::Result<Answer> {
+    if let Some(text) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        return Ok(Answer::from(text))
+    }
+
+    value.parse::<i128>()
+        .map(Answer::from)
+        .map_err(|_| anyhow!("Invalid answer '{value}', text answers must be quoted"))
+}
+
+//How a solver's answer compares with the recorded one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Mismatch { expected: Answer, actual: Answer },
+    Missing,
+}
+
+pub fn check(answers: &Answers, day: u8, part: u8, actual: &Answer) -> Verdict {
+    match answers.get(&(day, part)) {
+        Some(expected) if expected == actual => Verdict::Match,
+        Some(expected) => Verdict::Mismatch { expected: expected.clone(), actual: actual.clone() },
+        None => Verdict::Missing,
+    }
+}
+
+//Show both answers one above the other with a marker under the first character that differs
+pub fn diff(expected: &Answer, actual: &Answer) -> String {
+    let expected = expected.to_string();
+    let actual = actual.to_string();
+    let position = expected.chars()
+        .zip(actual.chars())
+        .take_while(|(e, a)| e == a)
+        .count();
+
+    format!("expected: {expected}\n  actual: {actual}\n          {}^", " ".repeat(position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_numbers_and_text() {
+        let answers = parse_answers("# comment\n[day18]\npart1 = 302\npart2 = \"24,32\"\n\n[day13]\npart2 = 101406661266314\n").unwrap();
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers[&(18, 1)], Answer::Int(302));
+        assert_eq!(answers[&(18, 2)], Answer::Text("24,32".into()));
+        assert_eq!(answers[&(13, 2)], Answer::Int(101406661266314));
+    }
+
+    #[test]
+    fn parse_errors_report_the_line() {
+        let error = parse_answers("[day1]\npart1 = 1\npart3 = 2").unwrap_err();
+        assert!(error.to_string().starts_with("Line 3"), "{error}");
+
+        assert!(parse_answers("part1 = 1").is_err());
+        assert!(parse_answers("[day1]\npart1 = 1\npart1 = 2").is_err());
+        assert!(parse_answers("[day1]\npart1 = 24,32").is_err());
+        assert!(parse_answers("[dayone]").is_err());
+    }
+
+    #[test]
+    fn check_against_recorded_answers() {
+        let answers = parse_answers("[day17]\npart1 = \"4,6,1,4,2,1,3,1,6\"").unwrap();
+
+        assert_eq!(check(&answers, 17, 1, &Answer::from("4,6,1,4,2,1,3,1,6")), Verdict::Match);
+        assert_eq!(check(&answers, 17, 2, &Answer::Int(7)), Verdict::Missing);
+        assert_eq!(
+            check(&answers, 17, 1, &Answer::Int(0)),
+            Verdict::Mismatch { expected: Answer::from("4,6,1,4,2,1,3,1,6"), actual: Answer::Int(0) }
+        );
+    }
+
+    #[test]
+    fn diff_marks_the_first_difference() {
+        assert_eq!(
+            diff(&Answer::Int(7371), &Answer::Int(7381)),
+            "expected: 7371\n  actual: 7381\n            ^"
+        );
+        assert_eq!(
+            diff(&Answer::from("6,1"), &Answer::from("6,1,2")),
+            "expected: 6,1\n  actual: 6,1,2\n             ^"
+        );
+    }
+}
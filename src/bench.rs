@@ -0,0 +1,223 @@
+//Timing harness for the bench command
+//
+//Each day's parse step (where it has one), part 1 and part 2 are timed separately over a number of
+//iterations. Results can be saved as a JSON baseline and later runs compared against it.
+
+use std::{collections::BTreeMap, fmt::Display, path::Path, time::{Duration, Instant}};
+
+use anyhow::{anyhow, Context};
+
+use crate::json::Json;
+
+pub const DEFAULT_ITERATIONS: usize = 10;
+pub const BASELINE_FILE: &str = "bench_baseline.json";
+
+//A median has to be this much slower than the baseline to count as a regression,
+//and by more than the noise floor so tiny timings don't trip it
+pub const REGRESSION_PERCENT: f64 = 10.0;
+pub const NOISE_FLOOR: Duration = Duration::from_micros(100);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Phase> {
+        match key {
+            "parse" => Some(Phase::Parse),
+            "part1" => Some(Phase::Part1),
+            "part2" => Some(Phase::Part2),
+            _ => None,
+        }
+    }
+
+    pub fn for_part(part: u8) -> Phase {
+        if part == 1 { Phase::Part1 } else { Phase::Part2 }
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part 1"),
+            Phase::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Timing> {
+        if samples.is_empty() {
+            return None
+        }
+
+        samples.sort();
+
+        //Even sample counts take the mean of the middle two
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Some(Timing { min: samples[0], median, max: samples[samples.len() - 1] })
+    }
+}
+
+//Run f the given number of times, stopping at the first error
+pub fn time<T>(iterations: usize, mut f: impl FnMut() -> anyhow::Result<T>) -> anyhow::Result<Timing> {
+    let mut samples = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let result = f()?;
+        samples.push(start.elapsed());
+        std::hint::black_box(result);
+    }
+
+    Timing::from_samples(samples).ok_or_else(|| anyhow!("At least one iteration is needed"))
+}
+
+//Saved timings keyed by (day, phase)
+pub type Baseline = BTreeMap<(u8, Phase), Timing>;
+
+pub fn baseline_to_json(iterations: usize, baseline: &Baseline) -> Json {
+    let nanos = |d: Duration| Json::Int(d.as_nanos() as i128);
+
+    Json::object([
+        ("iterations", Json::Int(iterations as i128)),
+        ("results", Json::Array(baseline.iter()
+            .map(|(&(day, phase), timing)| Json::object([
+                ("day", Json::Int(day as i128)),
+                ("phase", Json::Str(phase.key().to_string())),
+                ("min_ns", nanos(timing.min)),
+                ("median_ns", nanos(timing.median)),
+                ("max_ns", nanos(timing.max)),
+            ]))
+            .collect())),
+    ])
+}
+
+pub fn baseline_from_json(json: &Json) -> anyhow::Result<Baseline> {
+    let results = json.get("results")
+        .and_then(Json::as_array)
+        .ok_or_else(|| anyhow!("Baseline has no results"))?;
+
+    results.iter()
+        .map(|result| {
+            let nanos = |key: &str| result.get(key)
+                .and_then(Json::as_int)
+                .and_then(|n| u64::try_from(n).ok())
+                .map(Duration::from_nanos);
+            let day = result.get("day").and_then(Json::as_int).and_then(|d| u8::try_from(d).ok());
+            let phase = result.get("phase").and_then(Json::as_str).and_then(Phase::from_key);
+
+            match (day, phase, nanos("min_ns"), nanos("median_ns"), nanos("max_ns")) {
+                (Some(day), Some(phase), Some(min), Some(median), Some(max)) => Ok(((day, phase), Timing { min, median, max })),
+                _ => Err(anyhow!("Invalid baseline result {result}")),
+            }
+        })
+        .collect()
+}
+
+pub fn load_baseline(path: &Path) -> anyhow::Result<Baseline> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("Failed to read baseline {}", path.display()))?;
+
+    baseline_from_json(&Json::parse(&text)?).with_context(|| format!("Invalid baseline {}", path.display()))
+}
+
+pub fn save_baseline(path: &Path, iterations: usize, baseline: &Baseline) -> anyhow::Result<()> {
+    std::fs::write(path, format!("{}\n", baseline_to_json(iterations, baseline)))
+        .with_context(|| format!("Failed to write baseline {}", path.display()))
+}
+
+//Percentage change of the median against the baseline, positive is slower
+pub fn change_percent(median: Duration, baseline: Duration) -> f64 {
+    if baseline.is_zero() {
+        return 0.0
+    }
+
+    (median.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0
+}
+
+pub fn is_regression(median: Duration, baseline: Duration) -> bool {
+    change_percent(median, baseline) > REGRESSION_PERCENT && median.saturating_sub(baseline) > NOISE_FLOOR
+}
+
+//Durations with three significant-ish digits in a sensible unit
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    match nanos {
+        0..1_000 => format!("{nanos}ns"),
+        1_000..1_000_000 => format!("{:.2}µs", nanos as f64 / 1e3),
+        1_000_000..1_000_000_000 => format!("{:.2}ms", nanos as f64 / 1e6),
+        _ => format!("{:.2}s", nanos as f64 / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_millis(v)).collect()
+    }
+
+    #[test]
+    fn timing_takes_min_median_and_max() {
+        let odd = Timing::from_samples(millis(&[5, 1, 3])).unwrap();
+        assert_eq!(odd, Timing { min: Duration::from_millis(1), median: Duration::from_millis(3), max: Duration::from_millis(5) });
+
+        let even = Timing::from_samples(millis(&[4, 1, 2, 8])).unwrap();
+        assert_eq!(even.median, Duration::from_millis(3));
+
+        assert!(Timing::from_samples(vec![]).is_none());
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let baseline = Baseline::from([
+            ((1, Phase::Parse), Timing::from_samples(millis(&[1, 2, 3])).unwrap()),
+            ((17, Phase::Part2), Timing::from_samples(millis(&[40])).unwrap()),
+        ]);
+        let json = Json::parse(&baseline_to_json(3, &baseline).to_string()).unwrap();
+
+        assert_eq!(baseline_from_json(&json).unwrap(), baseline);
+    }
+
+    #[test]
+    fn regressions_need_to_beat_the_threshold_and_noise() {
+        assert!(is_regression(Duration::from_millis(12), Duration::from_millis(10)));
+        assert!(!is_regression(Duration::from_millis(11), Duration::from_millis(10)));
+        assert!(!is_regression(Duration::from_micros(20), Duration::from_micros(10)));
+        assert!(!is_regression(Duration::from_millis(5), Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn durations_pick_a_unit() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.50ms");
+        assert_eq!(format_duration(Duration::from_secs(3)), "3.00s");
+    }
+}
@@ -0,0 +1,344 @@
+//Command-line parsing for the puzzle runner
+//
+//Usage:
+//  adventofcode_24 run <days> [--part <1|2>] [--input <file|->] [--input-dir <dir>]
+//  adventofcode_24 verify [<days>] [--part <1|2>] [--answers <file>] ...
+//  adventofcode_24 bench [<days>] [--part <1|2>] [--iterations <n>] [--baseline <file>] [--save-baseline] [--slow] ...
+//
+//Where <days> is one of:
+//  16          - a single day
+//  5..12       - an inclusive range of days (5..=12 is also accepted)
+//  1,3,5..7    - a comma separated list of days and ranges
+//  all         - every day
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail};
+
+use crate::input_utils::{InputLocator, InputSource};
+use crate::log::Level;
+
+pub const FIRST_DAY: u8 = 1;
+pub const LAST_DAY: u8 = 25;
+
+pub const USAGE: &str = "\
+Usage: adventofcode_24 <command> [options]
+
+Commands:
+  run <days>               Run the solvers for the selected days
+  verify [days]            Check the selected days (default all) against the recorded answers
+  bench [days]             Time the selected days (default all) and compare against a saved baseline
+  help                     Show this message
+
+Days:
+  16                       A single day
+  5..12                    An inclusive range of days
+  1,3,5..7                 A comma separated list of days and ranges
+  all                      Every day
+
+Options:
+  -p, --part <1|2>         Only run the given part
+  -i, --input <file|->     Read the puzzle input from a file, or - for stdin (single day only)
+  -d, --input-dir <dir>    Read dayN.txt inputs from this directory
+                           (defaults to $AOC_INPUT_DIR, then ./input)
+  -a, --answers <file>     Recorded answers to verify against (defaults to ./answers.toml)
+  -n, --iterations <n>     Number of times bench runs each step (defaults to 10)
+  -b, --baseline <file>    Baseline bench compares against (defaults to ./bench_baseline.json)
+      --save-baseline      Save this bench run as the new baseline
+                           (a baseline is always saved when there isn't one yet)
+      --slow               Also bench the parts that take minutes (day 17 part 2), skipped otherwise
+  -v, --verbose            Log more detail to stderr, repeat (-vv, -vvv) for debug and trace output
+  -q, --quiet              Only log errors
+  -f, --format <text|json> Print results as text (default) or as one JSON record per day/part";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(RunOptions),
+    Verify(RunOptions),
+    Bench(RunOptions),
+    Help,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    pub days: Vec<u8>,
+    pub part: Option<u8>,
+    pub locator: InputLocator,
+    pub answers: Option<PathBuf>,
+    pub iterations: Option<usize>,
+    pub baseline: Option<PathBuf>,
+    pub save_baseline: bool,
+    pub include_slow: bool,
+    pub log_level: Level,
+    pub format: OutputFormat,
+}
+
+pub fn parse_args<I>(args: I) -> anyhow::Result<Command>
+    where I: IntoIterator<Item = String> {
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        Some("run") => parse_run(args, false).map(Command::Run),
+        Some("verify") => parse_run(args, true).map(Command::Verify),
+        Some("bench") => parse_run(args, true).map(Command::Bench),
+        Some("help") | Some("-h") | Some("--help") | None => Ok(Command::Help),
+        Some(other) => bail!("Unknown command '{other}'"),
+    }
+}
+
+//Run, verify and bench share their options, verify and bench use every day when none are selected
+fn parse_run<I>(mut args: I, all_by_default: bool) -> anyhow::Result<RunOptions>
+    where I: Iterator<Item = String> {
+    let mut days: Option<Vec<u8>> = None;
+    let mut part: Option<u8> = None;
+    let mut locator = InputLocator::new();
+    let mut options = RunOptions::default();
+    let mut verbose: u8 = 0;
+    let mut quiet = false;
+
+    while let Some(arg) = args.next() {
+        //Options can be given as either "--name value" or "--name=value"
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if arg.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = || inline_value.clone()
+            .or_else(|| args.next())
+            .ok_or_else(|| anyhow!("Missing value for {name}"));
+
+        match name.as_str() {
+            "-p" | "--part" => part = Some(parse_part(&value()?)?),
+            "-i" | "--input" => locator = locator.with_input(InputSource::parse(&value()?)),
+            "-d" | "--input-dir" => locator = locator.with_input_dir(value()?),
+            "-a" | "--answers" => options.answers = Some(PathBuf::from(value()?)),
+            "-n" | "--iterations" => options.iterations = Some(parse_iterations(&value()?)?),
+            "-b" | "--baseline" => options.baseline = Some(PathBuf::from(value()?)),
+            "--save-baseline" => options.save_baseline = true,
+            "--slow" => options.include_slow = true,
+            "-f" | "--format" => options.format = parse_format(&value()?)?,
+            "-v" | "--verbose" => verbose += 1,
+            "-q" | "--quiet" => quiet = true,
+            //Verbosity can be stacked as -vv or -vvv
+            _ if arg.len() > 2 && arg[1..].chars().all(|c| c == 'v') => verbose += (arg.len() - 1) as u8,
+            //A lone "-" would be stdin, but only as the value of --input
+            _ if arg.starts_with('-') => bail!("Unknown option '{arg}'"),
+            _ => {
+                if days.is_some() {
+                    bail!("Unexpected argument '{arg}', days have already been selected");
+                }
+                days = Some(parse_days(&arg)?);
+            },
+        }
+    }
+
+    let days = match days {
+        Some(days) => days,
+        None if all_by_default => (FIRST_DAY..=LAST_DAY).collect(),
+        None => bail!("No days selected, try 'run all'"),
+    };
+
+    //An input file only holds one day's puzzle
+    if locator.input.is_some() && days.len() > 1 {
+        bail!("--input can only be used when a single day is selected");
+    }
+
+    Ok(RunOptions { days, part, locator, log_level: Level::from_verbosity(verbose, quiet), ..options })
+}
+
+fn parse_format(value: &str) -> anyhow::Result<OutputFormat> {
+    match value {
+        "text" => Ok(OutputFormat::Text),
+        "json" => Ok(OutputFormat::Json),
+        _ => bail!("Format must be text or json, got '{value}'"),
+    }
+}
+
+fn parse_iterations(value: &str) -> anyhow::Result<usize> {
+    match value.parse() {
+        Ok(iterations) if iterations > 0 => Ok(iterations),
+        _ => bail!("Iterations must be a positive number, got '{value}'"),
+    }
+}
+
+fn parse_part(value: &str) -> anyhow::Result<u8> {
+    match value {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => bail!("Part must be 1 or 2, got '{value}'"),
+    }
+}
+
+pub fn parse_days(selection: &str) -> anyhow::Result<Vec<u8>> {
+    if selection == "all" {
+        return Ok((FIRST_DAY..=LAST_DAY).collect())
+    }
+
+    let mut days: Vec<u8> = Vec::new();
+
+    for item in selection.split(',') {
+        if let Some((from, to)) = item.split_once("..") {
+            //Accept both 5..12 and 5..=12, either way the range is inclusive
+            let to = to.strip_prefix('=').unwrap_or(to);
+            let from = parse_day(from)?;
+            let to = parse_day(to)?;
+
+            if from > to {
+                bail!("Invalid range '{item}', {from} is after {to}");
+            }
+
+            days.extend(from..=to);
+        } else {
+            days.push(parse_day(item)?);
+        }
+    }
+
+    //Run each day once and in order
+    days.sort();
+    days.dedup();
+
+    Ok(days)
+}
+
+fn parse_day(value: &str) -> anyhow::Result<u8> {
+    let day: u8 = value.trim().parse().map_err(|_| anyhow!("Invalid day '{value}'"))?;
+
+    if !(FIRST_DAY..=LAST_DAY).contains(&day) {
+        bail!("Day must be between {FIRST_DAY} and {LAST_DAY}, got {day}");
+    }
+
+    Ok(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_single_day() {
+        assert_eq!(parse_days("16").unwrap(), vec![16]);
+    }
+
+    #[test]
+    fn parse_range_is_inclusive() {
+        assert_eq!(parse_days("5..12").unwrap(), (5..=12).collect::<Vec<u8>>());
+        assert_eq!(parse_days("5..=12").unwrap(), (5..=12).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn parse_list_of_days_and_ranges() {
+        assert_eq!(parse_days("7,1,3..4,3").unwrap(), vec![1, 3, 4, 7]);
+    }
+
+    #[test]
+    fn parse_all_days() {
+        assert_eq!(parse_days("all").unwrap().len(), 25);
+    }
+
+    #[test]
+    fn parse_invalid_days() {
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("12..5").is_err());
+        assert!(parse_days("x").is_err());
+    }
+
+    #[test]
+    fn parse_run_command() {
+        assert_eq!(
+            parse_args(args("run 16 --part 2")).unwrap(),
+            Command::Run(RunOptions { days: vec![16], part: Some(2), ..Default::default() })
+        );
+        assert_eq!(
+            parse_args(args("run all")).unwrap(),
+            Command::Run(RunOptions { days: (1..=25).collect(), part: None, ..Default::default() })
+        );
+        assert_eq!(
+            parse_args(args("run -p 1 5..6")).unwrap(),
+            Command::Run(RunOptions { days: vec![5, 6], part: Some(1), ..Default::default() })
+        );
+    }
+
+    #[test]
+    fn parse_verify_command() {
+        assert_eq!(
+            parse_args(args("verify")).unwrap(),
+            Command::Verify(RunOptions { days: (1..=25).collect(), part: None, ..Default::default() })
+        );
+        assert_eq!(
+            parse_args(args("verify 17 -p 1 --answers=mine.toml")).unwrap(),
+            Command::Verify(RunOptions { days: vec![17], part: Some(1), answers: Some("mine.toml".into()), ..Default::default() })
+        );
+    }
+
+    #[test]
+    fn parse_bench_command() {
+        assert_eq!(
+            parse_args(args("bench 18 -n 5 --baseline=old.json --save-baseline --slow")).unwrap(),
+            Command::Bench(RunOptions {
+                days: vec![18],
+                iterations: Some(5),
+                baseline: Some("old.json".into()),
+                save_baseline: true,
+                include_slow: true,
+                ..Default::default()
+            })
+        );
+        assert!(parse_args(args("bench -n 0")).is_err());
+        assert!(parse_args(args("bench -x")).is_err());
+        assert!(parse_args(args("bench -n many")).is_err());
+    }
+
+    #[test]
+    fn parse_output_format() {
+        let Command::Run(options) = parse_args(args("run 18 --format json")).unwrap() else {
+            panic!("Expected a run command");
+        };
+
+        assert_eq!(options.format, OutputFormat::Json);
+        assert!(parse_args(args("run 18 -f yaml")).is_err());
+    }
+
+    #[test]
+    fn parse_verbosity() {
+        let level = |line: &str| match parse_args(args(line)).unwrap() {
+            Command::Run(options) => options.log_level,
+            _ => panic!("Expected a run command"),
+        };
+
+        assert_eq!(level("run 1"), Level::Warn);
+        assert_eq!(level("run 1 -v"), Level::Info);
+        assert_eq!(level("run 1 -v --verbose"), Level::Debug);
+        assert_eq!(level("run 1 -vvv"), Level::Trace);
+        assert_eq!(level("run 1 -vv --quiet"), Level::Error);
+    }
+
+    #[test]
+    fn parse_input_options() {
+        let Command::Run(options) = parse_args(args("run 3 --input - --input-dir=inputs")).unwrap() else {
+            panic!("Expected a run command");
+        };
+
+        assert_eq!(options.locator, InputLocator::new().with_input(InputSource::Stdin).with_input_dir("inputs"));
+        assert!(parse_args(args("run 3..4 --input day3.txt")).is_err());
+    }
+
+    #[test]
+    fn parse_run_command_errors() {
+        assert!(parse_args(args("run")).is_err());
+        assert!(parse_args(args("run 1 --part 3")).is_err());
+        assert!(parse_args(args("run 1 --part")).is_err());
+        assert!(parse_args(args("run 1 2")).is_err());
+        assert!(parse_args(args("fly 1")).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+//The runner's commands, each takes the options parsed by the cli module
+
+use std::{path::PathBuf, time::{Duration, Instant}};
+
+use colored::Colorize;
+
+use crate::answers::{self, Verdict};
+use crate::bench::{self, Phase, Timing};
+use crate::cli::{OutputFormat, RunOptions};
+use crate::report::{RunRecord, Status};
+use crate::solver::{self, Solution, Solver};
+use crate::{input_utils, log};
+
+//Settings shared by every command that runs solvers
+fn configure(options: &RunOptions) -> anyhow::Result<()> {
+    log::set_level(options.log_level);
+    input_utils::set_locator(options.locator.clone())
+}
+
+//Solve the selected days and print the answers
+pub fn run(options: &RunOptions) -> anyhow::Result<()> {
+    configure(options)?;
+
+    for &day in &options.days {
+        let solutions: Vec<&Solution> = solver::select(&[day], options.part).collect();
+
+        //Only read the input if there's something to solve
+        let input = if solutions.iter().any(|s| s.is_solved()) {
+            match input_utils::read_input(day) {
+                Ok(input) => Some(input),
+                Err(e) => {
+                    match options.format {
+                        OutputFormat::Text => println!("{} {e:#}", format!("Day {day} failed:").red()),
+                        OutputFormat::Json => solutions.iter()
+                            .map(|s| RunRecord::failed(day, s.part(), &e, None))
+                            .for_each(|record| println!("{}", record.to_json())),
+                    }
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+
+        for solution in solutions {
+            let record = match &input {
+                Some(input) if solution.is_solved() => {
+                    let start = Instant::now();
+                    let result = solution.solve(input);
+                    let elapsed = start.elapsed();
+
+                    match result {
+                        Ok(answer) => RunRecord::solved(day, solution.part(), answer, elapsed),
+                        Err(e) => RunRecord::failed(day, solution.part(), &e, Some(elapsed)),
+                    }
+                },
+                _ => RunRecord::unsolved(day, solution.part()),
+            };
+
+            match options.format {
+                OutputFormat::Text => print_record(&record),
+                OutputFormat::Json => println!("{}", record.to_json()),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+//Re-run the solvers and compare against the recorded answers, returns false if any part is wrong or fails
+pub fn verify(options: &RunOptions) -> anyhow::Result<bool> {
+    configure(options)?;
+
+    let answers_file = options.answers.clone().unwrap_or_else(answers::default_answers_file);
+    let recorded = answers::load_answers(&answers_file)?;
+
+    println!("Verifying against {}", answers_file.display());
+
+    let (mut matched, mut mismatched, mut missing, mut failed) = (0, 0, 0, 0);
+
+    for &day in &options.days {
+        let solutions: Vec<&Solution> = solver::select(&[day], options.part)
+            .filter(|s| s.is_solved())
+            .collect();
+
+        if solutions.is_empty() {
+            continue;
+        }
+
+        let input = match input_utils::read_input(day) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("{} {e:#}", format!("Day {day} failed:").red());
+                failed += solutions.len();
+                continue;
+            }
+        };
+
+        for solution in solutions {
+            let label = format!("Day {day} - Part {}:", solution.part());
+
+            match solution.solve(&input) {
+                Ok(actual) => match answers::check(&recorded, day, solution.part(), &actual) {
+                    Verdict::Match => {
+                        matched += 1;
+                        println!("{label} {} {actual}", "match".green());
+                    },
+                    Verdict::Mismatch { expected, actual } => {
+                        mismatched += 1;
+                        println!("{label} {}", "MISMATCH".red().bold());
+                        answers::diff(&expected, &actual).lines().for_each(|line| println!("    {line}"));
+                    },
+                    Verdict::Missing => {
+                        missing += 1;
+                        println!("{label} {} {actual}", "missing".yellow());
+                    },
+                },
+                Err(e) => {
+                    failed += 1;
+                    println!("{label} {} {e:#}", "failed".red());
+                },
+            }
+        }
+    }
+
+    println!();
+    println!("{matched} matched, {mismatched} mismatched, {missing} missing, {failed} failed");
+
+    Ok(mismatched == 0 && failed == 0)
+}
+
+//Time each day's steps and compare with the baseline, returns false if anything regressed
+pub fn bench(options: &RunOptions) -> anyhow::Result<bool> {
+    configure(options)?;
+
+    let iterations = options.iterations.unwrap_or(bench::DEFAULT_ITERATIONS);
+    let baseline_file = options.baseline.clone().unwrap_or_else(|| PathBuf::from(bench::BASELINE_FILE));
+    let baseline = if baseline_file.is_file() {
+        Some(bench::load_baseline(&baseline_file)?)
+    } else {
+        None
+    };
+
+    let mut timings = bench::Baseline::new();
+    let mut regressions = 0;
+
+    println!("Timing each step over {iterations} iterations");
+    println!("Days with a parse time of n/a don't parse separately, their part timings include parsing");
+    println!("{:>3}  {:<6}  {:>10}  {:>10}  {:>10}", "Day", "Step", "Min", "Median", "Max");
+
+    for &day in &options.days {
+        let solutions: Vec<&Solution> = solver::select(&[day], options.part)
+            .filter(|s| s.is_solved())
+            .collect();
+
+        if solutions.is_empty() {
+            continue;
+        }
+
+        let input = match input_utils::read_input(day) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("{} {e:#}", format!("Day {day} failed:").red());
+                continue;
+            }
+        };
+
+        //None for steps that aren't timed
+        let mut steps: Vec<(Phase, Option<anyhow::Result<Timing>>)> = Vec::new();
+
+        let mut skipped: Vec<Phase> = Vec::new();
+
+        let parser = solver::find_parser(day);
+        steps.push((Phase::Parse, parser.map(|parser| bench::time(iterations, || parser.parse(&input)))));
+
+        for solution in solutions {
+            let phase = Phase::for_part(solution.part());
+
+            if solution.is_slow() && !options.include_slow {
+                skipped.push(phase);
+                continue;
+            }
+            steps.push((phase, Some(bench::time(iterations, || solution.solve(&input)))));
+        }
+
+        for (phase, timing) in steps {
+            match timing {
+                None => println!("{day:>3}  {phase:<6}  {:>10}  {:>10}  {:>10}", "n/a", "n/a", "n/a"),
+                Some(Ok(timing)) => {
+                    let previous = baseline.as_ref().and_then(|b| b.get(&(day, phase))).map(|t| t.median);
+
+                    if print_timing(day, phase, &timing, previous) {
+                        regressions += 1;
+                    }
+                    timings.insert((day, phase), timing);
+                },
+                Some(Err(e)) => println!("{day:>3}  {phase:<6}  {} {e:#}", "failed:".red()),
+            }
+        }
+
+        for phase in skipped {
+            println!("{day:>3}  {phase:<6}  {}", "skipped, this part is slow (use --slow to time it)".yellow());
+        }
+    }
+
+    if options.save_baseline || baseline.is_none() {
+        //Keep the saved timings of any days that weren't run this time
+        let mut saved = baseline.unwrap_or_default();
+        saved.extend(timings);
+        bench::save_baseline(&baseline_file, iterations, &saved)?;
+        println!("Saved baseline to {}", baseline_file.display());
+    }
+
+    if regressions > 0 {
+        println!("{}", format!("{regressions} step(s) regressed by more than {}%", bench::REGRESSION_PERCENT).red().bold());
+    }
+
+    Ok(regressions == 0)
+}
+
+//Print one row of the bench table, returns true if it regressed against the baseline median
+fn print_timing(day: u8, phase: Phase, timing: &Timing, baseline: Option<Duration>) -> bool {
+    let row = format!(
+        "{day:>3}  {phase:<6}  {:>10}  {:>10}  {:>10}",
+        bench::format_duration(timing.min),
+        bench::format_duration(timing.median),
+        bench::format_duration(timing.max),
+    );
+
+    let Some(baseline) = baseline else {
+        println!("{row}");
+        return false
+    };
+
+    let change = format!("{:+.1}% vs {}", bench::change_percent(timing.median, baseline), bench::format_duration(baseline));
+
+    if bench::is_regression(timing.median, baseline) {
+        println!("{row}  {} {change}", "REGRESSED".red().bold());
+        true
+    } else {
+        println!("{row}  {change}");
+        false
+    }
+}
+
+fn print_record(record: &RunRecord) {
+    let RunRecord { day, part, .. } = record;
+
+    match (record.status(), &record.answer, &record.error) {
+        (Status::Solved, Some(answer), _) => {
+            println!("Day {day} - Part {part}:");
+            println!("{} {}", format!("Part {part} answer:").green(), answer.to_string().blue().bold());
+        },
+        (Status::Error, _, Some(error)) => {
+            println!("Day {day} - Part {part}:");
+            println!("{} {error}", format!("Part {part} failed:").red());
+        },
+        _ => println!("{}", format!("Day {day} - Part {part} is not solved yet").yellow()),
+    }
+}
@@ -0,0 +1,226 @@
+//Contracts a grid maze into a graph of junctions joined by corridors
+//
+//Every walkable cell that isn't simply part of a corridor becomes a node: junctions, dead ends and
+//any landmark cells (like a start or an end) asked for. Corridors between nodes become edges, which
+//can bend along the way and record their length and how many turns they make.
+//A loop of corridor with no node on it has nowhere to attach to, so it is left out of the graph.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::misc_types::{Direction, Grid, Point};
+
+//A corridor leading out of a node
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Corridor {
+    //Id of the node at the far end
+    pub to: usize,
+    //Every cell walked, starting at this node and ending at the far one
+    pub cells: Vec<Point<i32>>,
+    //Steps from one node to the other
+    pub length: usize,
+    //Changes of direction part way along, not counting the way we face when setting off
+    pub turns: usize,
+    //Heading when leaving this node, and when arriving at the far one
+    pub first: Direction,
+    pub last: Direction,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Node {
+    pub position: Point<i32>,
+    pub corridors: Vec<Corridor>,
+    //Pruned nodes have no corridors and nothing leads to them
+    pub pruned: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CorridorGraph {
+    pub nodes: Vec<Node>,
+    ids: HashMap<Point<i32>, usize>,
+    landmarks: Vec<usize>,
+}
+
+impl CorridorGraph {
+    //walkable picks out the open cells, landmark ones are always made into nodes
+    pub fn build<T>(grid: &Grid<T>, walkable: impl Fn(&T) -> bool, landmark: impl Fn(&T) -> bool) -> CorridorGraph {
+        let is_open = |pos: Point<i32>| grid.get(pos).is_some_and(&walkable);
+        let exits = |pos: Point<i32>| Direction::CARDINAL.into_iter().filter(move |d| is_open(pos + d.to_point()));
+
+        let mut graph = CorridorGraph::default();
+
+        //Corridor cells have exactly two ways out, everything else open is a node
+        for (pos, cell) in grid.iter() {
+            if walkable(cell) && (landmark(cell) || exits(pos).count() != 2) {
+                let id = graph.nodes.len();
+                graph.ids.insert(pos, id);
+                graph.nodes.push(Node { position: pos, corridors: vec![], pruned: false });
+
+                if landmark(cell) {
+                    graph.landmarks.push(id);
+                }
+            }
+        }
+
+        for id in 0..graph.nodes.len() {
+            let position = graph.nodes[id].position;
+
+            for first in exits(position) {
+                //Follow the corridor until it reaches another node
+                let mut cells = vec![position, position + first.to_point()];
+                let mut heading = first;
+                let mut turns = 0;
+
+                while let Some(pos) = cells.last().copied().filter(|pos| !graph.ids.contains_key(pos)) {
+                    //A corridor cell has one way out besides the way we came in
+                    let next = exits(pos)
+                        .find(|&d| d != heading.opposite())
+                        .expect("Corridor cells have two exits");
+
+                    if next != heading {
+                        turns += 1;
+                        heading = next;
+                    }
+                    cells.push(pos + next.to_point());
+                }
+
+                let to = graph.ids[cells.last().unwrap()];
+                graph.nodes[id].corridors.push(Corridor { to, length: cells.len() - 1, cells, turns, first, last: heading });
+            }
+        }
+
+        graph
+    }
+
+    pub fn node_at(&self, position: Point<i32>) -> Option<usize> {
+        self.ids.get(&position).copied()
+    }
+
+    //Repeatedly remove dead ends that aren't landmarks, as no route between landmarks needs to go down them.
+    //Removing one can leave the node before it as a dead end, so pruning carries on back along the chain
+    //through bends and junctions until it reaches a node that still leads somewhere.
+    pub fn prune_dead_ends(&mut self) {
+        let mut queue: VecDeque<usize> = (0..self.nodes.len()).collect();
+
+        while let Some(id) = queue.pop_front() {
+            let node = &self.nodes[id];
+            if node.pruned || node.corridors.len() > 1 || self.landmarks.contains(&id) {
+                continue;
+            }
+
+            //A corridor that loops straight back to its own node doesn't lead anywhere either
+            let neighbours: Vec<usize> = node.corridors.iter().map(|c| c.to).filter(|&to| to != id).collect();
+
+            self.nodes[id].pruned = true;
+            self.nodes[id].corridors.clear();
+
+            for neighbour in neighbours {
+                self.nodes[neighbour].corridors.retain(|c| c.to != id);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    //Nodes that are still part of the graph
+    pub fn live_nodes(&self) -> impl Iterator<Item = (usize, &Node)> {
+        self.nodes.iter().enumerate().filter(|(_, node)| !node.pruned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(maze: &str) -> CorridorGraph {
+        let grid = Grid::parse(maze, Some).unwrap();
+        CorridorGraph::build(&grid, |&c| c != '#', |&c| c == 'S' || c == 'E')
+    }
+
+    fn corridor(graph: &CorridorGraph, from: Point<i32>, to: Point<i32>) -> &Corridor {
+        let to = graph.node_at(to).unwrap();
+        graph.nodes[graph.node_at(from).unwrap()].corridors.iter().find(|c| c.to == to).unwrap()
+    }
+
+    #[test]
+    fn bending_corridors_are_contracted() {
+        let graph = build("\
+#######
+#S....#
+#####.#
+#E....#
+#######");
+
+        //Only the start and end are nodes, joined by one corridor with two turns
+        assert_eq!(graph.nodes.len(), 2);
+
+        let (start, end) = (Point::new(1, 1), Point::new(1, 3));
+        let there = corridor(&graph, start, end);
+        assert_eq!((there.length, there.turns), (10, 2));
+        assert_eq!((there.first, there.last), (Direction::East, Direction::West));
+        assert_eq!(there.cells.len(), 11);
+
+        let back = corridor(&graph, end, start);
+        assert_eq!((back.length, back.turns), (10, 2));
+        assert_eq!((back.first, back.last), (Direction::East, Direction::West));
+    }
+
+    #[test]
+    fn junctions_and_dead_ends_are_nodes() {
+        let graph = build("\
+#######
+#S...E#
+###.###
+###.###
+#######");
+
+        //The junction below the corridor and the dead end at the bottom
+        assert_eq!(graph.nodes.len(), 4);
+        let junction = graph.node_at(Point::new(3, 1)).unwrap();
+        assert_eq!(graph.nodes[junction].corridors.len(), 3);
+        assert_eq!(corridor(&graph, Point::new(3, 1), Point::new(3, 3)).length, 2);
+        assert!(graph.node_at(Point::new(2, 1)).is_none());
+    }
+
+    #[test]
+    fn pruning_follows_bending_chains() {
+        //The branch off the main corridor bends twice and ends at a junction of two more dead ends
+        let mut graph = build("\
+#########
+#S.....E#
+###.#####
+###...###
+#####.###
+####...##
+#########");
+
+        assert_eq!(graph.live_nodes().count(), 6);
+
+        graph.prune_dead_ends();
+
+        //All that's left is the start and end joined by a straight corridor through the old junction
+        let live: Vec<Point<i32>> = graph.live_nodes().map(|(_, node)| node.position).collect();
+        assert_eq!(live, vec![Point::new(1, 1), Point::new(3, 1), Point::new(7, 1)]);
+        assert_eq!(graph.nodes[graph.node_at(Point::new(3, 1)).unwrap()].corridors.len(), 2);
+        assert!(graph.nodes.iter().flat_map(|n| &n.corridors).all(|c| !graph.nodes[c.to].pruned));
+    }
+
+    #[test]
+    fn pruning_keeps_landmarks_and_loops() {
+        //A dead end start, and a junction whose other two exits loop round back to it
+        let mut graph = build("\
+########
+#S.#...#
+##.#.#.#
+##.....#
+###E####
+########");
+
+        let junction = Point::new(4, 3);
+        let around = corridor(&graph, junction, junction);
+        assert_eq!((around.length, around.turns), (8, 3));
+
+        graph.prune_dead_ends();
+
+        assert_eq!(graph.live_nodes().count(), graph.nodes.len());
+        assert_eq!(graph.nodes[graph.node_at(junction).unwrap()].corridors.len(), 3);
+    }
+}
@@ -1,39 +1,33 @@
 //https://adventofcode.com/2024/day/1
 
-use std::{fs::File, io::{BufRead, BufReader}};
+use crate::{debug, trace};
 
-pub fn run() -> anyhow::Result<()> {
-    println!("Day 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day1.txt");
-
-    println!("Reading input from {}", input_file.display());
+pub fn parse_sorted_lists(input: &str) -> anyhow::Result<(Vec<i32>, Vec<i32>)> {
+    let mut left: Vec<i32> = Vec::new();
+    let mut right: Vec<i32> = Vec::new();
 
-    let file = File::open(input_file)?;
-    let reader = BufReader::new(file);
-    
-    let mut left = vec![0_i32; 1000];
-    let mut right = vec![0_i32; 1000];
-
-    for (index, line) in reader.lines().enumerate() {
-        let line = line?;
-        
+    for line in input.lines() {
         //Split line into two and push into lists
         let parts: Vec<&str> = line.split("   ").collect();
 
-        left[index] = parts[0].parse::<i32>()?;
-        right[index] = parts[1].parse::<i32>()?;
+        left.push(parts[0].parse::<i32>()?);
+        right.push(parts[1].parse::<i32>()?);
     }
     
     //Sort lists
-    println!("Sorting lists...");
+    debug!("Sorting lists...");
     left.sort();
     right.sort();
 
+    Ok((left, right))
+}
+
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
+    let (left, right) = parse_sorted_lists(input)?;
+
     //Compare each item in the list to the same index in the other and subtract one from the other
     let mut running_total = 0;
-    for i in 0..1000 {
+    for i in 0..left.len() {
         let l = left[i];
         let r = right[i];
 
@@ -41,24 +35,26 @@ pub fn run() -> anyhow::Result<()> {
         running_total += i32::abs(l - r);
 
     }
-    
-    println!("Part 1 - Total: {running_total}");
 
-    //Part 2...
+    Ok(running_total as i64)
+}
+
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    let (left, right) = parse_sorted_lists(input)?;
 
-    //Both lists are already sorted from Part 1...
+    //Both lists are sorted...
     //Starting with the first number in the left list
     let mut left_index = 0;
     //and the first number in the right list
     let mut right_index = 0;
 
-    let mut left_histo = vec![0_i32; 1000];
+    let mut left_histo = vec![0_i32; left.len()];
 
-    while left_index < 1000 && right_index < 1000 {
+    while left_index < left.len() && right_index < right.len() {
         
         match right[right_index].cmp(&left[left_index]) {
             std::cmp::Ordering::Equal => {
-                println!("MATCH! [{left_index}]{} = [{right_index}]{}", left[left_index], right[right_index]);
+                trace!("MATCH! [{left_index}]{} = [{right_index}]{}", left[left_index], right[right_index]);
                 //The left and right numbers match
                 //Increment our histogram count
                 left_histo[left_index] += 1;
@@ -67,14 +63,14 @@ pub fn run() -> anyhow::Result<()> {
                 right_index += 1;
             },
             std::cmp::Ordering::Greater => {
-                println!("Greater-than [{left_index}]{} < [{right_index}]{}", left[left_index], right[right_index]);
+                trace!("Greater-than [{left_index}]{} < [{right_index}]{}", left[left_index], right[right_index]);
                 //We've moved to a number in the right list that is larger than the current item in the left list
                 //we need to look for a number in the left list that matches or is greater than the number in the right list
                 //Move to the next number in the left list
                 left_index += 1;
             },
             std::cmp::Ordering::Less => {
-                println!("Less-than [{left_index}]{} > [{right_index}]{}", left[left_index], right[right_index]);
+                trace!("Less-than [{left_index}]{} > [{right_index}]{}", left[left_index], right[right_index]);
                 //If the number in the right list is now smaller than the number in the left list
                 //we didn't find it in the left list so move to the next number in the right list
                 right_index += 1;
@@ -82,13 +78,35 @@ pub fn run() -> anyhow::Result<()> {
         }
     }
 
+    //Repeated numbers in the left list share the count found for the first of them
+    for index in 1..left.len() {
+        if left[index] == left[index - 1] {
+            left_histo[index] = left_histo[index - 1];
+        }
+    }
+
     let mut running_total: i64 = 0;
 
     for (index, val) in left_histo.into_iter().enumerate() {
         running_total += (left[index] * val) as i64;
     }
 
-    println!("Part 2 - Total: {running_total}");
+    Ok(running_total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(())
-}
\ No newline at end of file
+    const EXAMPLE: &str = include_str!("../input/day1-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(11, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(31, solve_part2(EXAMPLE).unwrap());
+    }
+}
@@ -1,68 +1,37 @@
-#![allow(clippy::type_complexity)]
 //https://adventofcode.com/2024/day/10
 
-use std::{collections::HashSet, fs::File, io::{BufRead, BufReader}};
-
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 10 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day10.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-    
-    let mut trailheads: Vec<(usize, usize)> = Vec::new();
-    let map: Vec<Vec<i8>> = reader.lines()
-        .enumerate()
-        .map(|(row, line)| 
-            line.unwrap()
-                .chars()
-                .enumerate()
-                .map(|(col, c)| {
-                    let height = c.to_digit(10).unwrap() as i8;
-                    if height == 0 {
-                        trailheads.push((col, row));
-                    }
-                    height
-                }).collect::<Vec<i8>>()
-            )
-        .collect();
+use crate::misc_types::{Direction, Grid, Point};
+use crate::trace;
+use std::collections::HashSet;
+
+pub fn parse_map(input: &str) -> anyhow::Result<Grid<i8>> {
+    Grid::parse(input, |c| c.to_digit(10).map(|height| height as i8))
+}
 
-    let map_width = map[0].len() as i32;
-    let map_height = map.len() as i32;
-    let bounds = (map_width, map_height);
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
+    let map = parse_map(input)?;
 
     //HashSet to record DISTINCT Origin->Summit hikes
-    let mut summits_reached: HashSet<((usize, usize), (usize, usize))> = HashSet::new();
-    trailheads.iter().for_each(|pos| {
-        walk_trail_path(&map, bounds, *pos, *pos, (0, 1), &mut summits_reached);
-        walk_trail_path(&map, bounds, *pos, *pos, (1, 0), &mut summits_reached);
-        walk_trail_path(&map, bounds, *pos, *pos, (0, -1), &mut summits_reached);
-        walk_trail_path(&map, bounds, *pos, *pos, (-1, 0), &mut summits_reached);
-    });
+    let mut summits_reached: HashSet<(Point<i32>, Point<i32>)> = HashSet::new();
+    for pos in map.positions(|&height| height == 0) {
+        for direction in Direction::CARDINAL {
+            walk_trail_path(&map, pos, pos, direction, &mut summits_reached);
+        }
+    }
 
-    summits_reached.iter().for_each(|(origin, summit_reached)| println!("{:?}->{:?}", *origin, *summit_reached));
+    summits_reached.iter().for_each(|(origin, summit_reached)| trace!("{origin}->{summit_reached}"));
 
     Ok(summits_reached.len() as i64)
 }
 
-fn walk_trail_path(map: &Vec<Vec<i8>>, bounds: (i32, i32), pos: (usize, usize), origin: (usize, usize), direction: (i32, i32), summits_reached: &mut HashSet<((usize, usize), (usize, usize))>) {
+fn walk_trail_path(map: &Grid<i8>, pos: Point<i32>, origin: Point<i32>, direction: Direction, summits_reached: &mut HashSet<(Point<i32>, Point<i32>)>) {
     //Recursively walk a trail until we can't climb any further or reach the summit (height 9)
-    let current_height = map[pos.1][pos.0];
-    let new_pos = (pos.0 as i32 + direction.0, pos.1 as i32 + direction.1);
+    let current_height = map[pos];
+    let new_pos = pos + direction.to_point();
 
     //Bounds check
-    if new_pos.0 >= 0
-    && new_pos.0 < bounds.0
-    && new_pos.1 >= 0
-    && new_pos.1 < bounds.1 {
-        let new_pos = (new_pos.0 as usize, new_pos.1 as usize);
-        let origin_direction = (-direction.0, -direction.1);
-
-        let new_height = map[new_pos.1][new_pos.0];
+    if let Some(&new_height) = map.get(new_pos) {
+        let origin_direction = direction.opposite();
 
         //Can we climb to the new position?
         if new_height - current_height == 1 {
@@ -70,37 +39,24 @@ fn walk_trail_path(map: &Vec<Vec<i8>>, bounds: (i32, i32), pos: (usize, usize),
                 summits_reached.insert((origin, new_pos));
             } else {
                 //Try and move in other 3 directions except the one we just came from
-                if origin_direction != (0, 1) {
-                    walk_trail_path(map, bounds, new_pos, origin, (0, 1), summits_reached);
-                }
-                if origin_direction != (1, 0) {
-                    walk_trail_path(map, bounds, new_pos, origin,  (1, 0), summits_reached);
-                }
-                if origin_direction != (0, -1) {
-                    walk_trail_path(map, bounds, new_pos, origin, (0, -1), summits_reached);
-                }
-                if origin_direction != (-1, 0) {
-                    walk_trail_path(map, bounds, new_pos, origin, (-1, 0), summits_reached);
+                for next_direction in Direction::CARDINAL {
+                    if next_direction != origin_direction {
+                        walk_trail_path(map, new_pos, origin, next_direction, summits_reached);
+                    }
                 }
             }
         }
     }
 }
 
-fn walk_trail_path_v(map: &Vec<Vec<i8>>, bounds: (i32, i32), pos: (usize, usize), origin: (usize, usize), direction: (i32, i32), summits_reached: &mut Vec<((usize, usize), (usize, usize))>) {
+fn walk_trail_path_v(map: &Grid<i8>, pos: Point<i32>, origin: Point<i32>, direction: Direction, summits_reached: &mut Vec<(Point<i32>, Point<i32>)>) {
     //Recursively walk a trail until we can't climb any further or reach the summit (height 9)
-    let current_height = map[pos.1][pos.0];
-    let new_pos = (pos.0 as i32 + direction.0, pos.1 as i32 + direction.1);
+    let current_height = map[pos];
+    let new_pos = pos + direction.to_point();
 
     //Bounds check
-    if new_pos.0 >= 0
-    && new_pos.0 < bounds.0
-    && new_pos.1 >= 0
-    && new_pos.1 < bounds.1 {
-        let new_pos = (new_pos.0 as usize, new_pos.1 as usize);
-        let origin_direction = (-direction.0, -direction.1);
-
-        let new_height = map[new_pos.1][new_pos.0];
+    if let Some(&new_height) = map.get(new_pos) {
+        let origin_direction = direction.opposite();
 
         //Can we climb to the new position?
         if new_height - current_height == 1 {
@@ -108,63 +64,43 @@ fn walk_trail_path_v(map: &Vec<Vec<i8>>, bounds: (i32, i32), pos: (usize, usize)
                 summits_reached.push((origin, new_pos));
             } else {
                 //Try and move in other 3 directions except the one we just came from
-                if origin_direction != (0, 1) {
-                    walk_trail_path_v(map, bounds, new_pos, origin, (0, 1), summits_reached);
-                }
-                if origin_direction != (1, 0) {
-                    walk_trail_path_v(map, bounds, new_pos, origin,  (1, 0), summits_reached);
-                }
-                if origin_direction != (0, -1) {
-                    walk_trail_path_v(map, bounds, new_pos, origin, (0, -1), summits_reached);
-                }
-                if origin_direction != (-1, 0) {
-                    walk_trail_path_v(map, bounds, new_pos, origin, (-1, 0), summits_reached);
+                for next_direction in Direction::CARDINAL {
+                    if next_direction != origin_direction {
+                        walk_trail_path_v(map, new_pos, origin, next_direction, summits_reached);
+                    }
                 }
             }
         }
     }
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 10 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day10.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
-    let mut trailheads: Vec<(usize, usize)> = Vec::new();
-    let map: Vec<Vec<i8>> = reader.lines()
-        .enumerate()
-        .map(|(row, line)| 
-            line.unwrap()
-                .chars()
-                .enumerate()
-                .map(|(col, c)| {
-                    let height = c.to_digit(10).unwrap() as i8;
-                    if height == 0 {
-                        trailheads.push((col, row));
-                    }
-                    height
-                }).collect::<Vec<i8>>()
-            )
-        .collect();
-
-    let map_width = map[0].len() as i32;
-    let map_height = map.len() as i32;
-    let bounds = (map_width, map_height);
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    let map = parse_map(input)?;
 
     //Vector to record ALL Origin->Summit hikes, including different paths
-    let mut summits_reached: Vec<((usize, usize), (usize, usize))> = Vec::new();
-    trailheads.iter().for_each(|pos| {
-        walk_trail_path_v(&map, bounds, *pos, *pos, (0, 1), &mut summits_reached);
-        walk_trail_path_v(&map, bounds, *pos, *pos, (1, 0), &mut summits_reached);
-        walk_trail_path_v(&map, bounds, *pos, *pos, (0, -1), &mut summits_reached);
-        walk_trail_path_v(&map, bounds, *pos, *pos, (-1, 0), &mut summits_reached);
-    });
-   
-   Ok(summits_reached.len() as i64)
-}
\ No newline at end of file
+    let mut summits_reached: Vec<(Point<i32>, Point<i32>)> = Vec::new();
+    for pos in map.positions(|&height| height == 0) {
+        for direction in Direction::CARDINAL {
+            walk_trail_path_v(&map, pos, pos, direction, &mut summits_reached);
+        }
+    }
+
+    Ok(summits_reached.len() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day10-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(36, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(81, solve_part2(EXAMPLE).unwrap());
+    }
+}
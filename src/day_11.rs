@@ -1,27 +1,18 @@
 //https://adventofcode.com/2024/day/11
 
-use std::{collections::HashMap, fs::File, io::{BufRead, BufReader}};
+use std::collections::HashMap;
 
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 11 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day11.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
-    let mut stones: Vec<u64> = reader.lines()
-        .next()
-        .map(|line| 
-            line.unwrap()
-            .split(' ')
-            .map(|s| 
-                s.parse::<u64>().unwrap()
-            ).collect::<Vec<u64>>()
-        ).unwrap();
+//The numbers engraved on the stones, in the order they're lined up
+pub fn parse_stones(input: &str) -> anyhow::Result<Vec<u64>> {
+    let line = input.lines().next().unwrap_or_default();
+
+    Ok(line.split_whitespace()
+        .map(|s| s.parse::<u64>())
+        .collect::<Result<Vec<u64>, _>>()?)
+}
+
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
+    let mut stones = parse_stones(input)?;
     
     let stop_at_blinks = 25;
     let mut blink_count = 0;
@@ -42,7 +33,7 @@ pub fn do_part1() -> anyhow::Result<i64> {
             } else {
                 let stone_str = stone.to_string();
 
-                if stone_str.len() % 2 == 0 {
+                if stone_str.len().is_multiple_of(2) {
                     //Even number of digits...split in two
                     let (left, right) = stone_str.split_at(stone_str.len() / 2);
                     let (left, right) = (left.parse::<u64>().unwrap(), right.parse::<u64>().unwrap());
@@ -68,26 +59,8 @@ enum StoneProduced {
     Double((u64, u64)),
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 11 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day11.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-   
-    let mut stones: Vec<u64> = reader.lines()
-        .next()
-        .map(|line| 
-            line.unwrap()
-            .split(' ')
-            .map(|s| 
-                s.parse::<u64>().unwrap()
-            ).collect::<Vec<u64>>()
-        ).unwrap();
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    let mut stones = parse_stones(input)?;
 
     //Part 2 we're blinking 75 times - this will take a lot longer if we use 
     //the simple solution from Part 1 and consume a lot of memory.
@@ -136,7 +109,7 @@ fn get_stones_produced(stone_cache: &mut HashMap<u64, StoneProduced>, stone_coun
             } else {
                 let stone_str = stone.to_string();
             
-                if stone_str.len() % 2 == 0 {
+                if stone_str.len().is_multiple_of(2) {
                     //Even number of digits...split in two
                     let (left, right) = stone_str.split_at(stone_str.len() / 2);
                     output = StoneProduced::Double((left.parse::<u64>().unwrap(), right.parse::<u64>().unwrap()));
@@ -165,4 +138,21 @@ fn get_stones_produced(stone_cache: &mut HashMap<u64, StoneProduced>, stone_coun
     }
 
     stones_produced
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day11-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(55312, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(65601038650482, solve_part2(EXAMPLE).unwrap());
+    }
+}
@@ -1,230 +1,163 @@
 //https://adventofcode.com/2024/day/12
 
+use crate::algorithms::label_components;
+use crate::geometry::{trace_outlines, Outline};
 use crate::misc_types::*;
-use std::{collections::HashSet, fs::File, io::{BufRead, BufReader}};
+use std::collections::HashSet;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Edge {
-    outside_dir: Point<i32>,
+    outside_dir: Direction,
     plot: Point<usize>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 struct Region {
     plant: char,
-    plots: Vec<Point<usize>>,
-    perimeter: Vec<Edge>,
+    plots: HashSet<Point<usize>>,
+    //Edges facing away from the region, and those facing into holes surrounded by it
+    outer_perimeter: Vec<Edge>,
+    inner_perimeter: Vec<Edge>,
+    //Smallest rectangle holding every plot
+    bounds: Rect<usize>,
 }
 
 impl Region {
-    fn new(plant: char) -> Region {
+    fn new(plant: char, plot: Point<usize>) -> Region {
         Region {
             plant,
-            plots: vec![],
-            perimeter: vec![],
+            plots: HashSet::new(),
+            outer_perimeter: vec![],
+            inner_perimeter: vec![],
+            bounds: Rect::new(plot.x, plot.y, plot.x + 1, plot.y + 1),
         }
     }
+
+    fn add_plot(&mut self, plot: Point<usize>) {
+        self.plots.insert(plot);
+        self.bounds = self.bounds.union(&Rect::new(plot.x, plot.y, plot.x + 1, plot.y + 1));
+    }
+
     fn area(&self) -> u32 {
         self.plots.len() as u32
     }
 
     fn perimeter_len(&self) -> u32 {
-        self.perimeter.len() as u32
+        (self.outer_perimeter.len() + self.inner_perimeter.len()) as u32
     }
 
-    fn number_of_sides(&mut self) -> u32 {
-        let directions = vec![
-            Point::new(0, -1),  //Up
-            Point::new(0, 1),   //down
-            Point::new(-1, 0),  //left
-            Point::new(1, 0),   //right
-        ];
-
-        let mut total_sides = 0;
-        //Prevent sorting when not required
-        let mut sorted_by_y = false;
-        self.perimeter.sort_by_key(|e| e.plot.x);
-
-        for direction in directions {
-
-            let mut previous_edge: Option<Point<usize>> = None;
-            let mut is_side_x = false;
-            let mut is_side_y = false;
-
-            //Sort by x or y depending on direction
-            if direction.x == 0 && !sorted_by_y {
-                self.perimeter.sort_by_key(|e| e.plot.y);
-                sorted_by_y = true;
-            } else if direction.y == 0 && sorted_by_y {
-                self.perimeter.sort_by_key(|e| e.plot.x);
-                sorted_by_y = false;
-            }
+    //Each corner of the region's outlines, including round any holes, starts a new side
+    fn number_of_sides(&self) -> u32 {
+        let cells = self.plots.iter().map(|plot| Point::new(plot.x as i32, plot.y as i32)).collect();
 
-            let mut sides = 0;
-            self.perimeter.iter()
-                .filter(|p| p.outside_dir == direction)
-                .for_each(|p| {
-
-                    if let Some(previous_edge) = previous_edge {
-                        //If we have a previous edge to compare...
-                        //Is the previous edge on the same side as the current edge?
-                        if p.plot.x == previous_edge.x && usize::abs_diff(p.plot.y,previous_edge.y) == 1 {
-                            if is_side_y {
-                                //We've changed sides
-                                sides += 1;
-                                is_side_y = false;
-                            }
-                            is_side_x = true;
-                        } else if p.plot.y == previous_edge.y && usize::abs_diff(p.plot.x,previous_edge.x) == 1 {
-                            if is_side_x {
-                                //We've changed sides
-                                sides += 1;
-                                is_side_x = false;
-                            }
-                            is_side_y = true;
-                        } else if is_side_x || is_side_y {
-                            //We were processing a side but came to the end of it
-                            sides += 1;
-                            is_side_x = false;
-                            is_side_y = false;
-                        } else {
-                            //Previous edge was a single side
-                            sides += 1;
-                        }
-                    }
-                    previous_edge = Some(p.plot);
-                });
-                //Add the last side
-                sides += 1;
-                total_sides += sides;
-        };
-
-        total_sides
+        trace_outlines(&cells).iter().map(Outline::sides).sum::<usize>() as u32
     }
 
     fn cost_by_permeter_len(&self) -> u32 {
         self.area() * self.perimeter_len()
     }
 
-    fn cost_by_side(&mut self) -> u32 {
+    fn cost_by_side(&self) -> u32 {
         self.area() * self.number_of_sides()
     }
 }
 
 struct Farm {
     map: Vec<Vec<char>>,
-    width: usize,
-    height: usize,
+    //Index into regions of the region each plot belongs to
+    region_ids: Vec<Vec<usize>>,
     regions: Vec<Region>,
-    directions: Vec<Point<i32>>,
 }
 
 impl Farm {
     fn new(map: Vec<Vec<char>>) -> Farm {
-        let width = map[0].len();
-        let height = map.len();
-
         Farm {
             map,
-            width,
-            height,
+            region_ids: Vec::new(),
             regions: Vec::new(),
-            directions: vec![
-                Point::new(0, -1),  //Up
-                Point::new(0, 1),   //down
-                Point::new(-1, 0),  //left
-                Point::new(1, 0),   //right
-            ],
-        }
-    }
-
-    fn find_regions(&mut self) {
-        //Map will be filled in as we discover regions
-        let mut y = 0;
-        while y < self.height{
-            if let Some((x, _)) = self.map[y].iter().enumerate().find(|(_, &c)| c != '.') {
-                let region = self.scan_region(Point::new(x, y));
-                self.regions.push(region);
-                self.fill_region('.', self.regions.len()-1);
-            } else {
-                y += 1;
-            }
         }
     }
 
-    fn scan_region(&mut self, start_point: Point<usize>) -> Region {
-        let mut region = Region::new(self.map[start_point.y][start_point.x]);
-
-        let mut visited: HashSet<Point<i32>> = HashSet::new();
-        self.probe_point(start_point.into(), &mut region, &mut visited);
-
-        region
+    //Id of the region holding the plot, None off the edge of the map
+    fn region_at(&self, pos: Point<i32>) -> Option<usize> {
+        let plot = Point::<usize>::try_from(pos).ok()?;
+        self.region_ids.get(plot.y)?.get(plot.x).copied()
     }
 
-    fn probe_point(&self, point: Point<i32>, region: &mut Region, visited: &mut HashSet<Point<i32>>) {
-        if visited.contains(&point) {
-            return
-        }
-
-        visited.insert(point);
-
-        if !self.inside(region.plant, point) {
-            return
-        } else {
-            region.plots.push(point.into());
-        }
-
-        //Look for connected plots, ignoring those that have already been visited
-        for &direction in &self.directions {
-            let target = point + direction; 
-
-            if !self.inside(region.plant, target) {
-                //Add to edges
-                region.perimeter.push(Edge { plot: point.into(), outside_dir: direction });
-                visited.insert(target);
+    //The edges round the outside of a region, found by walking its outline from the top of its first plot in
+    //reading order, as nothing of the region can be above that. Any of the region's other edges face into holes.
+    fn outer_edges(&self, id: usize, first: Point<usize>) -> Vec<Edge> {
+        let in_region = |pos: Point<i32>| self.region_at(pos) == Some(id);
+        let start = (Point::new(first.x as i32, first.y as i32), Direction::North);
+        let mut edges = vec![];
+
+        //Walk with the region on the right, each step moves on to the next edge round the corner ahead
+        let (mut plot, mut outside) = start;
+        loop {
+            edges.push(Edge { plot: Point::new(plot.x as usize, plot.y as usize), outside_dir: outside });
+
+            let heading = outside.turn_right();
+            let ahead = plot + heading.to_point();
+            let diagonal = ahead + outside.to_point();
+
+            (plot, outside) = if in_region(diagonal) {
+                (diagonal, heading.opposite())
+            } else if in_region(ahead) {
+                (ahead, outside)
             } else {
-                self.probe_point(target, region, visited);
+                (plot, heading)
+            };
+
+            if (plot, outside) == start {
+                return edges;
             }
         }
     }
-    
-    fn inside(&self, plant: char, location: Point<i32>) -> bool {
-        if !(location.x >= 0 && location.x < self.width.try_into().unwrap() && location.y >= 0 && location.y < self.height.try_into().unwrap()) {
-            //Outside map bounds
-            return false
-        } else if self.map[location.y as usize][location.x as usize] != plant {
-            return false
-        }
 
-        true
-    }
-
-    fn fill_region(&mut self, new_plant: char, region_idx: usize) {
-        for plot in &self.regions[region_idx].plots {
-            self.map[plot.y][plot.x] = new_plant;
+    fn find_regions(&mut self) {
+        //Label each region in one pass, rather than flood filling from each plot,
+        //then the labels give each region's plots and perimeter
+        (self.region_ids, _) = label_components(&self.map, |a, b| a == b);
+        let mut outer: HashSet<Edge> = HashSet::new();
+
+        for y in 0..self.region_ids.len() {
+            for x in 0..self.region_ids[y].len() {
+                let (id, plot) = (self.region_ids[y][x], Point::new(x, y));
+
+                //Labels count up in reading order, so a new one is always the next region
+                if id == self.regions.len() {
+                    self.regions.push(Region::new(self.map[y][x], plot));
+                    outer.extend(self.outer_edges(id, plot));
+                }
+                self.regions[id].add_plot(plot);
+
+                //Any side not shared with a plot of the same region is part of the perimeter
+                for direction in Direction::CARDINAL {
+                    if self.region_at(Point::new(x as i32, y as i32) + direction.to_point()) != Some(id) {
+                        let edge = Edge { plot, outside_dir: direction };
+                        let region = &mut self.regions[id];
+
+                        if outer.contains(&edge) {
+                            region.outer_perimeter.push(edge);
+                        } else {
+                            region.inner_perimeter.push(edge);
+                        }
+                    }
+                }
+            }
         }
     }
 }
 
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 12 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day12.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
+//The plant growing in each plot
+pub fn parse_map(input: &str) -> anyhow::Result<Vec<Vec<char>>> {
+    Ok(input.lines()
+        .map(|line| line.chars().collect())
+        .collect())
+}
 
-    let mut farm = Farm::new(reader.lines()
-        .map(|line| 
-            line.unwrap()
-                .chars()
-                .collect::<Vec<char>>()
-            )
-        .collect()
-    );
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
+    let mut farm = Farm::new(parse_map(input)?);
 
     farm.find_regions();
 
@@ -234,30 +167,85 @@ pub fn do_part1() -> anyhow::Result<i64> {
     Ok(total_cost as i64)
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 12 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day12.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-   
-    let mut farm = Farm::new(reader.lines()
-    .map(|line| 
-        line.unwrap()
-            .chars()
-            .collect::<Vec<char>>()
-        )
-        .collect()
-    );
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    let mut farm = Farm::new(parse_map(input)?);
 
     farm.find_regions();
 
-    let total_cost = farm.regions.iter_mut()
+    let total_cost = farm.regions.iter()
         .fold(0, |total, r| total + r.cost_by_side());
 
     Ok(total_cost as i64)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day12-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(1930, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(1206, solve_part2(EXAMPLE).unwrap());
+    }
+
+    fn farm(map: &str) -> Farm {
+        let mut farm = Farm::new(map.lines().map(|line| line.chars().collect()).collect());
+        farm.find_regions();
+        farm
+    }
+
+    #[test]
+    fn regions_with_holes() {
+        let farm = farm("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO");
+
+        //The Os surround four separate X regions
+        assert_eq!(farm.regions.len(), 5);
+        assert_eq!(farm.region_ids[1], vec![0, 1, 0, 2, 0]);
+        assert_eq!(farm.region_ids[3], vec![0, 3, 0, 4, 0]);
+
+        let outer = &farm.regions[0];
+        assert_eq!((outer.plant, outer.area()), ('O', 21));
+        assert_eq!((outer.outer_perimeter.len(), outer.inner_perimeter.len()), (20, 16));
+        assert_eq!(outer.bounds, Rect::new(0, 0, 5, 5));
+
+        let hole = &farm.regions[1];
+        assert_eq!((hole.plant, hole.area()), ('X', 1));
+        assert_eq!((hole.outer_perimeter.len(), hole.inner_perimeter.len()), (4, 0));
+        assert_eq!(hole.bounds, Rect::new(1, 1, 2, 2));
+        assert!(hole.plots.contains(&Point::new(1, 1)));
+    }
+
+    #[test]
+    fn holes_meeting_the_outside_at_a_corner() {
+        //The gap in the middle only touches the one in the corner diagonally, so it's a hole
+        let farm = farm("XXX\nX.X\nXX.");
+
+        let region = &farm.regions[0];
+        assert_eq!(region.area(), 7);
+        assert_eq!((region.outer_perimeter.len(), region.inner_perimeter.len()), (12, 4));
+    }
+
+    #[test]
+    fn holes_touching_each_other_at_a_corner() {
+        let farm = farm("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+
+        let region = &farm.regions[0];
+        assert_eq!((region.outer_perimeter.len(), region.inner_perimeter.len()), (24, 16));
+        assert_eq!(farm.regions[0].cost_by_side(), 28 * 12);
+    }
+
+    #[test]
+    fn large_region_doesnt_overflow_the_stack() {
+        let farm = farm(&vec!["A".repeat(500); 500].join("\n"));
+
+        assert_eq!(farm.regions.len(), 1);
+        assert_eq!(farm.regions[0].area(), 250_000);
+        assert_eq!(farm.regions[0].perimeter_len(), 2000);
+    }
+}
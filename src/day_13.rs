@@ -2,64 +2,14 @@
 #![allow(dead_code)]
 //https://adventofcode.com/2024/day/13
 
-use std::{fs::File, io::{BufRead, BufReader}};
+use std::{fmt::Display, str::FromStr};
 
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 13 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day13.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let mut reader = BufReader::new(file);
+use crate::input_utils::{scan_line, ScanError, ScanFields};
 
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
     let mut total_cost: i32 = 0;
 
-    loop {
-        let mut button_a_line = String::new();
-
-        if reader.read_line(&mut button_a_line).is_err() {
-            //Probably end of file
-            break;
-        }
-        if button_a_line.is_empty() {
-            break;
-        }
-
-        let mut button_b_line = String::new();
-        reader.read_line(&mut button_b_line)?;
-        let mut target_line = String::new();
-        reader.read_line(&mut target_line)?;
-        //Read blank line
-        reader.read_line(&mut target_line)?;
-
-        
-        let button_a: MachinePos<i32> = MachinePos::from(button_a_line.trim().split('+').skip(1).map(|s| { 
-            if let Some(idx) = s.find(',') {
-                s[..idx].parse::<i32>().unwrap()
-            } else {
-                s.parse::<i32>().unwrap()
-            }
-        }).collect::<Vec<i32>>());
-
-        let button_b: MachinePos<i32> = MachinePos::from(button_b_line.trim().split('+').skip(1).map(|s| { 
-            if let Some(idx) = s.find(',') {
-                s[..idx].parse::<i32>().unwrap()
-            } else {
-                s.parse::<i32>().unwrap()
-            }
-        }).collect::<Vec<i32>>());
-
-        let target: MachinePos<i32> = MachinePos::from(target_line.trim().split('=').skip(1).map(|s| { 
-            if let Some(idx) = s.find(',') {
-                s[..idx].parse::<i32>().unwrap()
-            } else {
-                s.parse::<i32>().unwrap()
-            }
-        }).collect::<Vec<i32>>());
-
+    for (button_a, button_b, target) in parse_machines::<i32>(input)? {
         let mut result = test_machine(button_a, button_b, target);
 
         if !result.is_empty() {
@@ -72,7 +22,7 @@ pub fn do_part1() -> anyhow::Result<i64> {
 }
 
 #[derive(Debug)]
-struct MachinePos<T> {
+pub struct MachinePos<T> {
     x: T,
     y: T,
 }
@@ -85,16 +35,41 @@ where T: Copy {
         }
     }
 
-    fn from(vals: Vec<T>) -> MachinePos<T> {
-        if vals.len() != 2 {
-            panic!()
-        }
+}
 
-        MachinePos {
-            x: vals[0],
-            y: vals[1],
-        }
+//Button A, button B and the prize location for each machine
+pub type Machine<T> = (MachinePos<T>, MachinePos<T>, MachinePos<T>);
+
+//Lines may be indented, the indent is skipped but errors still give the column in the whole line
+fn scan_indented<T: ScanFields>(line_number: usize, line: &str, format: &str) -> Result<T, ScanError> {
+    let indent = line[..line.len() - line.trim_start().len()].chars().count();
+
+    scan_line(line_number, line.trim(), format).map_err(|error| ScanError { column: error.column + indent, ..error })
+}
+
+pub fn parse_machines<T>(input: &str) -> anyhow::Result<Vec<Machine<T>>>
+where T: Copy + FromStr, T::Err: Display {
+    let mut machines = Vec::new();
+
+    //Machines are described by three lines, separated by a blank line
+    let mut lines = input.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(index, l)| (index + 1, l));
+
+    while let Some((line_number, button_a_line)) = lines.next() {
+        let (Some((button_b_number, button_b_line)), Some((target_number, target_line))) = (lines.next(), lines.next()) else {
+            anyhow::bail!("Incomplete machine description at line {line_number}: {}", button_a_line.trim());
+        };
+
+        let (ax, ay) = scan_indented(line_number, button_a_line, "Button A: X+{}, Y+{}")?;
+        let (bx, by) = scan_indented(button_b_number, button_b_line, "Button B: X+{}, Y+{}")?;
+        let (x, y) = scan_indented(target_number, target_line, "Prize: X={}, Y={}")?;
+
+        machines.push((MachinePos::new(ax, ay), MachinePos::new(bx, by), MachinePos::new(x, y)));
     }
+
+    Ok(machines)
 }
 
 #[derive(Debug)]
@@ -186,62 +161,10 @@ fn test_machine_with_cramers_rule(button_a: MachinePos<i128>, button_b: MachineP
     }
 }
 
-pub fn do_part2() -> anyhow::Result<i128> {
-    println!("Day 13 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day13.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let mut reader = BufReader::new(file);
-   
+pub fn solve_part2(input: &str) -> anyhow::Result<i128> {
     let mut total_cost: i128 = 0;
 
-    loop {
-        let mut button_a_line = String::new();
-
-        if reader.read_line(&mut button_a_line).is_err() {
-            //Probably end of file
-            break;
-        }
-        if button_a_line.is_empty() {
-            break;
-        }
-
-        let mut button_b_line = String::new();
-        reader.read_line(&mut button_b_line)?;
-        let mut target_line = String::new();
-        reader.read_line(&mut target_line)?;
-        //Read blank line
-        reader.read_line(&mut target_line)?;
-
-        
-        let button_a = MachinePos::from(button_a_line.trim().split('+').skip(1).map(|s| { 
-            if let Some(idx) = s.find(',') {
-                s[..idx].parse::<i128>().unwrap()
-            } else {
-                s.parse::<i128>().unwrap()
-            }
-        }).collect::<Vec<i128>>());
-
-        let button_b = MachinePos::from(button_b_line.trim().split('+').skip(1).map(|s| { 
-            if let Some(idx) = s.find(',') {
-                s[..idx].parse::<i128>().unwrap()
-            } else {
-                s.parse::<i128>().unwrap()
-            }
-        }).collect::<Vec<i128>>());
-
-        let mut target = MachinePos::from(target_line.trim().split('=').skip(1).map(|s| { 
-            if let Some(idx) = s.find(',') {
-                s[..idx].parse::<i128>().unwrap()
-            } else {
-                s.parse::<i128>().unwrap()
-            }
-        }).collect::<Vec<i128>>());
-
+    for (button_a, button_b, mut target) in parse_machines::<i128>(input)? {
         target.x += 10000000000000;
         target.y += 10000000000000;
 
@@ -252,4 +175,39 @@ pub fn do_part2() -> anyhow::Result<i128> {
     }
     
     Ok(total_cost)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day13-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(480, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn malformed_machine_reports_the_line() {
+        let input = EXAMPLE.replacen("Prize: X=8400, Y=5400", "Prize: X=8400 Y=5400", 1);
+        let error = solve_part1(&input).unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 10: expected a value followed by ', Y=', found '8400 Y=5400'");
+    }
+
+    #[test]
+    fn malformed_indented_machine_reports_the_column() {
+        let input = EXAMPLE.replacen("Prize: X=8400, Y=5400", "  Prize: X=8400 Y=5400", 1);
+        let error = solve_part1(&input).unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 12: expected a value followed by ', Y=', found '8400 Y=5400'");
+
+        //Indents on their own are fine
+        let indented = EXAMPLE.lines().map(|line| format!("    {line}")).collect::<Vec<_>>().join("\n");
+        assert_eq!(solve_part1(&indented).unwrap(), solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(875318608908, solve_part2(EXAMPLE).unwrap());
+    }
+}
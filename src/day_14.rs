@@ -1,169 +1,113 @@
 //https://adventofcode.com/2024/day/14
 
-use crate::input_utils::parse_formatted;
+use crate::input_utils::scan_lines;
 use crate::misc_types::*;
-use std::{fs::File, io::{BufRead, BufReader, Write}};
+use anyhow::bail;
+use std::collections::HashSet;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct Robot {
-    position: Vector2D,
-    velocity: Vector2D,
+pub struct Robot {
+    position: Point<i32>,
+    velocity: Point<i32>,
 }
 
 impl Robot {
     fn new(pos_x: i32, pos_y: i32, velocity_x: i32, velocity_y: i32) -> Robot {
         Robot { 
-            position: Vector2D::new(pos_x, pos_y),
-            velocity: Vector2D::new(velocity_x, velocity_y),
+            position: Point::new(pos_x, pos_y),
+            velocity: Point::new(velocity_x, velocity_y),
          }
     }
 
-    fn ensure_position_wrapped(&mut self, map_width: i32, map_height: i32) {
-        if self.position.x < 0 {
-            self.position.x += map_width;
-        }
-        if self.position. y < 0 {
-            self.position.y += map_height;
-        }
-        if self.position.x >= map_width {
-            self.position.x -= map_width;
-        }
-        if self.position.y >= map_height {
-            self.position.y -= map_height;
-        }
+    //The robots teleport to the other side when they walk off the edge
+    fn position_after(&self, seconds: i32, area: Rect) -> Point<i32> {
+        area.wrap(self.position + self.velocity * seconds)
     }
 }
 
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 14 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day14.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-    let mut robots: Vec<Robot> = Vec::new();
-
-    for line in reader.lines() {
-        let line = line?;
-
-        let robot_params: Vec<i32> = parse_formatted(line, "p={},{} v={},{}".into())
-            .iter()
-            .map(|s| s.parse::<i32>().unwrap())
-            .collect();
-        robots.push(Robot::new(
-            robot_params[0],
-            robot_params[1],
-            robot_params[2],
-            robot_params[3],
-        ));
-    }
+pub fn parse_robots(input: &str) -> anyhow::Result<Vec<Robot>> {
+    let robots = scan_lines(input, "p={},{} v={},{}")?
+        .into_iter()
+        .map(|(pos_x, pos_y, velocity_x, velocity_y)| Robot::new(pos_x, pos_y, velocity_x, velocity_y))
+        .collect();
 
-    let width: i32 = 101;
-    let height: i32 = 103;
+    Ok(robots)
+}
 
-    for _ in 0..100 {
-        for robot in &mut robots {
-            robot.position += robot.velocity;
-            robot.ensure_position_wrapped(width, height);
-        }
-    }
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
+    safety_factor(input, 101, 103)
+}
 
-    let quadrants: [Rect; 4] = [
-        Rect::new(0,0, 50, 51),
-        Rect::new(51,0, 101, 51),
-        Rect::new(0,52, 50, 103),
-        Rect::new(51,52, 101, 103),
-    ];
+//Multiply the robot counts in each quadrant after 100 seconds, robots on the middle lines don't count
+fn safety_factor(input: &str, width: i32, height: i32) -> anyhow::Result<i64> {
+    let area = Rect::from_size(width, height);
+    let positions: Vec<Point<i32>> = parse_robots(input)?
+        .iter()
+        .map(|robot| robot.position_after(100, area))
+        .collect();
 
     let mut safety_factor = 1;
     
-    for quadrant in quadrants {
-        safety_factor *= robots.iter()
-            .filter(|&r| r.position.x >= quadrant.left 
-                && r.position.x < quadrant.right 
-                && r.position.y >= quadrant.top 
-                && r.position.y < quadrant.bottom 
-            ).count();
+    for quadrant in area.quadrants() {
+        safety_factor *= positions.iter().filter(|&&p| quadrant.is_inside(p)).count();
     }
 
     Ok(safety_factor as i64)
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 14 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day14.txt");
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    first_picture(input, 101, 103)
+}
 
-    println!("Reading input from {}", input_file.display());
+//Seconds until the robots first spread out so that none of them overlap
+fn first_picture(input: &str, width: i32, height: i32) -> anyhow::Result<i64> {
+    let mut robots = parse_robots(input)?;
 
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
+    let area = Rect::from_size(width, height);
 
-    let mut robots: Vec<Robot> = Vec::new();
+    //The robots all wrap back to where they started after width * height seconds
+    //The picture of the tree is the first arrangement where no two robots share a position
+    for seconds in 0..area.area() {
+        let positions: HashSet<Point<i32>> = robots.iter().map(|r| r.position).collect();
 
-    for line in reader.lines() {
-        let line = line?;
+        if positions.len() == robots.len() {
+            return Ok(seconds as i64)
+        }
 
-        let robot_params: Vec<i32> = parse_formatted(line, "p={},{} v={},{}".into())
-            .iter()
-            .map(|s| s.parse::<i32>().unwrap())
-            .collect();
-        robots.push(Robot::new(
-            robot_params[0],
-            robot_params[1],
-            robot_params[2],
-            robot_params[3],
-        ));
+        for robot in &mut robots {
+            robot.position = robot.position_after(1, area);
+        }
     }
 
-    let width: i32 = 101;
-    let height: i32 = 103;
-    let mut map: Vec<Vec<i32>> = vec![vec![0; 101]; 103];
-
-    let mut output_file = std::env::current_dir()?;
-    output_file.push("output\\day14_map.txt");
-
-    println!("Writing output to {}", output_file.display());
-
-    let mut file = File::create(output_file.clone())?;
-
-    //Every 103 seconds from 57 secs onwards seems to be where a pattern is forming
-    //Every 101 seconds from 98 secs onwards seems to be where the pattern is forming
-
-    //Mark-I eyeball evaluation of output!
-    for iteration in 0..10000 {
-        for robot in &mut robots {
-            if map[robot.position.y as usize][robot.position.x as usize] != 0 {
-                map[robot.position.y as usize][robot.position.x as usize] -= 1;
-            }
+    bail!("The robots never formed a picture")
+}
 
-            robot.position += robot.velocity;
-            robot.ensure_position_wrapped(width, height);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            map[robot.position.y as usize][robot.position.x as usize] += 1;
-        }
+    const EXAMPLE: &str = include_str!("../input/day14-test.txt");
 
-        if (iteration - 98) % 101 == 0 {
-            write_map_to_file(&mut file, &map, iteration)?;
-        }
+    #[test]
+    fn part1_example() {
+        assert_eq!(12, safety_factor(EXAMPLE, 11, 7).unwrap());
     }
 
-    Ok(7371)
-}
-
-fn write_map_to_file(file: &mut File, map: &Vec<Vec<i32>>, iteration: i32) -> anyhow::Result<()> {
-    writeln!(file, "Iteration: {iteration}")?;
+    #[test]
+    fn part2_example() {
+        //Two of the example robots start on the same tile
+        assert_eq!(1, first_picture(EXAMPLE, 11, 7).unwrap());
+    }
 
-    for row in map {
-        for col in row {
-            write!(file, "{}", if *col == 0 { " " } else { "*" })?;
-        }
-        writeln!(file)?;
+    #[test]
+    fn part2_waits_for_every_overlap_to_clear() {
+        //The moving robot lands on a different stationary one for the first two seconds
+        let robots = "p=0,0 v=0,0\np=0,0 v=1,0\np=1,0 v=0,0\np=2,0 v=0,0";
+        assert_eq!(3, first_picture(robots, 5, 5).unwrap());
     }
 
-    Ok(())
-}
\ No newline at end of file
+    #[test]
+    fn part2_robots_that_always_overlap() {
+        assert!(first_picture("p=1,1 v=1,1\np=1,1 v=1,1", 5, 5).is_err());
+    }
+}
@@ -1,450 +1,194 @@
 //https://adventofcode.com/2024/day/16
 
-use std::{cmp::Ordering, collections::{BinaryHeap, HashSet}, fs::File, io::{BufRead, BufReader}, vec};
-use crate::misc_types::Point;
-
-#[derive(Clone, Debug, PartialEq)]
-struct Node {
-    id: usize,
-    position: Point<usize>,
-    edges: Vec<Edge>,
-    is_valid: bool,
-}
-
-impl Node {
-    fn new(id: usize, x: usize, y: usize) -> Node {
-        Node {
-            id,
-            position: Point::new(x, y),
-            edges: vec![],
-            is_valid: true,
-        }
-    }
-}
-
+use std::collections::HashSet;
+use crate::corridor_graph::{Corridor, CorridorGraph};
+use crate::misc_types::{Direction, Grid, Point};
+use crate::pathfinding::{dijkstra, dijkstra_all};
+use crate::{debug, log};
+use anyhow::anyhow;
+use colored::Colorize;
+
+/// A corridor walked between two nodes of the [`CorridorGraph`], given by their ids.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-struct Edge {
-    from: usize,
-    to: usize,
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
 }
 
 impl Edge {
-    fn new(from: usize, to: usize) -> Edge {
+    pub fn new(from: usize, to: usize) -> Edge {
         Edge {
-            from, 
+            from,
             to,
         }
     }
 }
 
-#[derive(Clone, Eq, PartialEq)]
-struct State {
-    cost: usize,
-    node_id: usize,
-    prev_node_id: Option<usize>,
-    path: Vec<Edge>,
-}
+//Turning costs depend on the way we're facing, so the searches are over (node id, facing) states
+type State = (usize, Direction);
 
-// The priority queue depends on `Ord`.
-// Explicitly implement the trait so the queue becomes a min-heap
-// instead of a max-heap.
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // Notice that we flip the ordering on costs.
-        // In case of a tie we compare positions - this step is necessary
-        // to make implementations of `PartialEq` and `Ord` consistent.
-        other.cost.cmp(&self.cost)
-            .then_with(|| self.node_id.cmp(&other.node_id))
+//Quarter turns needed to face the other way
+fn rotations(facing: Direction, heading: Direction) -> usize {
+    if facing == heading {
+        0
+    } else if facing == heading.opposite() {
+        2
+    } else {
+        1
     }
 }
 
-// `PartialOrd` needs to be implemented as well.
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+//Score for walking a corridor, 1 per step and 1000 per turn, including any turn to face down it first
+fn calc_cost(corridor: &Corridor, facing: Direction) -> usize {
+    corridor.length + 1000 * (rotations(facing, corridor.first) + corridor.turns)
 }
 
+//The states reachable along each corridor from a node, with the cost of walking it
+fn moves<'a>(graph: &'a CorridorGraph, &(node_id, facing): &State) -> impl Iterator<Item = (State, usize)> + 'a {
+    graph.nodes[node_id].corridors
+        .iter()
+        .map(move |corridor| ((corridor.to, corridor.last), calc_cost(corridor, facing)))
+}
 
-fn calc_cost(from: Point<usize>, to: Point<usize>, current_direction: Point<i32>) -> usize {
-    let p1: Point<i32> = from.into();
-    let p2: Point<i32> = to.into();
-    let diff = p2 - p1;
+//Build the maze graph, returning it with the ids of the start and end nodes
+pub fn parse_maze(input: &str) -> anyhow::Result<(Grid<char>, CorridorGraph, usize, usize)> {
+    let grid = Grid::parse(input, Some)?;
 
-    //Calculate Manhattan cost, diagonal movement is not allowed
-    let mut cost = (diff.x.abs() + diff.y.abs()) as usize;
+    //Dead ends never help us find the goal, maze's have plenty of them
+    let mut graph = CorridorGraph::build(&grid, |&c| c != '#', |&c| c == 'S' || c == 'E');
+    graph.prune_dead_ends();
 
-    if current_direction != get_direction(p1, p2) {
-        cost += 1000;
-    }
+    let find = |marker: char| grid.find(|&c| c == marker)
+        .and_then(|pos| graph.node_at(pos))
+        .ok_or_else(|| anyhow!("The maze has no {marker}"));
+    let (start, end) = (find('S')?, find('E')?);
 
-    cost
+    Ok((grid, graph, start, end))
 }
 
-fn get_direction(p1: Point<i32>, p2: Point<i32>) -> Point<i32> {
-    let mut diff: Point<i32> = p2 - p1;
-
-    diff = diff.clamp(-1, 1);
-
-    diff
+/// The cheapest route found by [`shortest_path`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortestPathResult {
+    /// Total score of the route, 1 per step plus 1000 for each change of direction.
+    pub shortest_path_cost: usize,
+    /// The corridors walked, in order from the start.
+    pub shortest_path: Vec<Edge>,
 }
 
-//Find nodes and edges and update map with their node id at their location
-//All positive numbers in the map are nodes id's
-fn find_nodes(map: &mut [Vec<i32>], nodes: &mut Vec<Node>) -> (usize, usize) {
-    //Convert the map into a set of nodes and edges
-    //First: Identify the nodes, these are:
-    // - the start and end locations
-    // - any point that is a junction or turn (not a corridor)
-    // - any point that is a dead end
-
-    let bounds: Point<usize> = Point::new(map[0].len(), map.len());
-
-    let mut start: usize = 0;
-    let mut end: usize = 0;
-
-    for y in 1..bounds.y-1 {
-        for x in 1..bounds.x-1 {
-            match map[y][x] {
-                //Empty space
-                -1 => {
-                    //If its not a corridor or completely enclosed...
-                    if !((map[y][x-1] >= -1
-                    && map[y][x+1] >= -1
-                    && map[y-1][x] == -2
-                    && map[y+1][x] == -2)
-                    || (map[y-1][x] >= -1
-                    && map[y+1][x] >= -1
-                    && map[y][x-1] == -2
-                    && map[y][x+1] == -2))
-                    && (map[y][x-1] >= -1
-                    || map[y][x+1] >= -1
-                    || map[y-1][x] >= -1
-                    || map[y+1][x] >= -1) {
-                        let id = nodes.len();
-                        map[y][x] = id as i32;
-                        nodes.push(Node::new(id, x, y));
-                    }
-                },
-                //Wall
-                -2 => { /* Not a node */},
-                //Start location
-                -3 => {
-                    start = nodes.len();
-                    map[y][x] = start as i32;
-                    nodes.push(Node::new(start, x, y));
-                },
-                //End location
-                -4 => {
-                    end = nodes.len();
-                    map[y][x] = end as i32;
-                    nodes.push(Node::new(end, x, y));
-                },
-                _ => { /* Ignored */ },
-            };
-        }
-    }
-    
-    let directions: [Point<i32>; 4] = [
-        Point::new(0, -1),
-        Point::new(1, 0),
-        Point::new(0, 1),
-        Point::new(-1, 0),
-    ];
-
-    //A set of the unvisited nodes - any nodes left unvisited can be marked as invalid
-    let mut not_visited: HashSet<usize> = nodes.iter().map(|n| n.id).collect();
-
-    //Connect nodes together, starting with the start node, identify all edges
-    let mut nodes_to_check: Vec<usize> = vec![start];
-    while let Some(node_id) = nodes_to_check.pop() {
-        if !not_visited.contains(&node_id) {
-            continue;
-        }
+/// Dijkstra's shortest path over the maze graph, scoring each step as 1 and each quarter turn as 1000.
+///
+/// `start` and `goal` are node ids in `graph`, the search starts facing `start_direction`.
+/// Returns `None` if the goal can't be reached.
+///
+/// ```
+/// use adventofcode_24::corridor_graph::CorridorGraph;
+/// use adventofcode_24::day_16::{shortest_path, Edge};
+/// use adventofcode_24::misc_types::{Direction, Grid, Point};
+///
+/// //Start at (1,1) facing East, walk 3 East then turn and walk 2 South
+/// let grid = Grid::parse("######\n#S...#\n####.#\n####E#\n######", Some).unwrap();
+/// let graph = CorridorGraph::build(&grid, |&c| c != '#', |&c| c == 'S' || c == 'E');
+/// let (start, end) = (graph.node_at(Point::new(1, 1)).unwrap(), graph.node_at(Point::new(4, 3)).unwrap());
+///
+/// let result = shortest_path(&graph, start, end, Direction::East).unwrap();
+/// assert_eq!(result.shortest_path_cost, 1005);
+/// assert_eq!(result.shortest_path, vec![Edge::new(start, end)]);
+/// ```
+pub fn shortest_path(graph: &CorridorGraph, start: usize, goal: usize, start_direction: Direction) -> Option<ShortestPathResult> {
+    let path = dijkstra((start, start_direction), |state| moves(graph, state), |&(node_id, _)| node_id == goal)?;
+
+    Some(ShortestPathResult {
+        shortest_path_cost: path.cost,
+        shortest_path: path.nodes.windows(2).map(|w| Edge::new(w[0].0, w[1].0)).collect(),
+    })
+}
 
-        //Look in each direction for another node
-        for direction in directions {
-            let mut curr_pos: Point<i32> = nodes[node_id].position.into();
-            loop {
-                let new_pos: Point<usize> = (curr_pos + direction).into();
-                let cell_value = map[new_pos.y][new_pos.x];
-                if cell_value >= 0 {
-                    //We found another node, create an edge
-                    nodes[node_id].edges.push(Edge::new(node_id, cell_value as usize));
-                    nodes_to_check.push(cell_value as usize);
-                    break;
-                } else if cell_value == -2 {
-                    //Hit a wall 
-                    break;
-                }
-
-                curr_pos = new_pos.into();
-            }
-        }
-        not_visited.remove(&node_id);
-    }
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
+    let (_, graph, start, end) = parse_maze(input)?;
 
-    //Remove edges that don't help us find the goal - Maze's have dead ends
-
-    //Nodes with only 1 edge are dead ends, mark as invalid (exluding the starting node)
-    //and remove any edges from other nodes that point to the invalid node
-    // - repeat and until all nodes have at least 1 edge pointing to a valid node
-    for node_id in 0..nodes.len() {
-        if nodes[node_id].edges.len() == 1 && !(node_id == end || node_id == start) {
-            nodes[node_id].is_valid = false;
-            let mut node_to_inspect = nodes[node_id].edges[0].to;
-            let mut prev_node = node_id;
-            loop {
-                if nodes[node_to_inspect].edges.len() == 2 && node_to_inspect != start {
-                    
-                    nodes[node_to_inspect].is_valid = false;
-
-                    if let Some(next_node) = nodes[node_to_inspect].edges
-                        .iter()
-                        .filter(|e|e.to != prev_node)
-                        .map(|e| e.to)
-                        .next() {
-                        prev_node = node_to_inspect;
-                        node_to_inspect = next_node;
-                        
-                    } else {
-                        break;
-                    }
-                } else {
-                    break;
-                }
-            }
-        }
-    }
+    //Start facing East
+    let start_direction = Direction::East;
 
-    (start, end)
-}
+    let ShortestPathResult { shortest_path_cost, .. } = shortest_path(&graph, start, end, start_direction)
+        .ok_or_else(|| anyhow!("No route from the start to the end"))?;
 
-struct ShortestPathResult {
-    shortest_path_cost: usize,
-    shortest_path: Vec<Edge>,
+    Ok(shortest_path_cost as i64)
 }
-// Dijkstra's shortest path algorithm
-
-// Start at `start` and use `dist` to track the current shortest distance
-// to each node. This implementation isn't memory-efficient as it may leave duplicate
-// nodes in the queue. It also uses `usize::MAX` as a sentinel value,
-// for a simpler implementation.
-fn shortest_path(adj_list: &[Node], start: usize, goal: usize, start_direction: Point<i32>) -> Option<ShortestPathResult> {
-    // dist[node] = current shortest distance from `start` to `node`
-    let mut dist: Vec<_> = (0..adj_list.len()).map(|_| usize::MAX).collect();
-    let mut heap = BinaryHeap::new();
-
-    // We're at `start`, with a zero cost
-    dist[start] = 0;
-    heap.push(State { cost: 0, node_id: start, prev_node_id: None, path: Vec::new() });
-
-    // Examine the frontier with lower cost nodes first (min-heap)
-    while let Some(State { cost, node_id, prev_node_id, path }) = heap.pop() {
-        if node_id == goal {
-            //Need to include the goal edge
-            let mut path = path;
-
-            if let Some(prev_node_id) = prev_node_id {
-                path.push(Edge { from: prev_node_id, to: node_id });
-            }
-
-            return Some(ShortestPathResult {
-                shortest_path_cost: cost,
-                shortest_path: path,
-            })
-        }
 
-        // Important as we may have already found a better way
-        if cost > dist[node_id] || !adj_list[node_id].is_valid { continue; }
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    let (grid, graph, start, end) = parse_maze(input)?;
 
-        // For each node we can reach, see if we can find a way with
-        // a lower cost going through this node
-        for edge in &adj_list[node_id].edges {
-            let mut previous_path = path.clone();
+    //Start facing East, a single search records every way of reaching each state at its cheapest cost
+    let start_state = (start, Direction::East);
+    let all_paths = dijkstra_all(start_state, |state| moves(&graph, state), |&(node_id, _)| node_id == end)
+        .ok_or_else(|| anyhow!("No route from the start to the end"))?;
 
-            let current_direction = if let Some(prev_node_id) = prev_node_id {
-                previous_path.push(Edge { from: prev_node_id, to: node_id });
-                get_direction(adj_list[prev_node_id].position.into(), adj_list[node_id].position.into())
-            } else {
-                start_direction
-            };
+    //Collect the tiles of every corridor on a best path
+    let mut tiles: HashSet<Point<i32>> = HashSet::from([graph.nodes[start].position]);
 
-            let edge_cost = calc_cost(adj_list[node_id].position, adj_list[edge.to].position, current_direction);
-            
-            let next = State { 
-                cost: cost + edge_cost, 
-                node_id: edge.to, 
-                prev_node_id: Some(node_id),
-                path: previous_path, 
-            };
+    for ((from, facing), (to, heading)) in all_paths.steps() {
+        //Two corridors can join the same nodes, the step was made along whichever is cheapest
+        let corridors: Vec<&Corridor> = graph.nodes[from].corridors
+            .iter()
+            .filter(|c| c.to == to && c.last == heading)
+            .collect();
+        let cheapest = corridors.iter().map(|c| calc_cost(c, facing)).min().unwrap_or_default();
 
-            // If so, add it to the frontier and continue
-            if next.cost < dist[next.node_id] {
-                // Relaxation, we have now found a better way
-                dist[next.node_id] = next.cost;
-                heap.push(next);
-            }
+        for corridor in corridors.into_iter().filter(|c| calc_cost(c, facing) == cheapest) {
+            tiles.extend(corridor.cells.iter().copied());
         }
     }
-    
-    None
-}
-
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 16 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day16.txt");
-
-    println!("Reading input from {}", input_file.display());
 
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-    let mut map: Vec<Vec<i32>> = vec![];
-
-    for line in reader.lines() {
-        let line = line?;
-
-        let row: Vec<i32> = line.chars().map(|c| match c {
-            '#' => -2,  //Wall
-            'S' => -3,  //Start location
-            'E' => -4,  //End location
-            _ => -1,    //Empty space
-        }).collect();
-        map.push(row);
+    if log::enabled(log::Level::Debug) {
+        debug!("Best path tiles:\n{}", render_map(&grid, &graph, &tiles));
     }
 
-    let mut nodes: Vec<Node> = vec![];
-
-    let (start, end) = find_nodes(&mut map, &mut nodes);
-
-    //Start facing East
-    let start_direction = Point::new(1, 0);
-
-    if let Some(ShortestPathResult {shortest_path_cost, ..}) = shortest_path(&nodes, start, end, start_direction) {
-        Ok(shortest_path_cost as i64)
-    } else {
-        Ok(0)
-    }
+    Ok(tiles.len() as i64)
 }
 
-fn find_optimal_paths(nodes: &[Node], start: usize, end: usize, start_direction: Point<i32>) -> HashSet<Edge> {
-    let mut path_nodes: HashSet<Edge> = HashSet::new();
-
-    //Get the optimal path and walk through it an edge at a time
-    //While the edge.to node is not the goal...
-    //If the edge.to node has multiple choices excluding the node it has come from
-    //Try to see if there is a route to the goal with the same cost as the optimal route
-
-    let mut prev_node_id: Option<usize> = None;
-    if let Some(ShortestPathResult {shortest_path_cost, shortest_path: path}) = shortest_path(nodes, start, end, start_direction) {
-        for edge in path {
-            path_nodes.insert(edge);
-
-            let current_node_id: usize = edge.from;
-            if let Some(prev_node_id) = prev_node_id {
-                //if we're at a junction
-                let other_edges = nodes[current_node_id].edges.iter().filter(|e| e.to != prev_node_id);
-
-                if other_edges.clone().count() > 1 {
-                    for edge in other_edges {
-                        if let Some(ShortestPathResult { shortest_path_cost: cost_from_start, .. }) = shortest_path(nodes, start, edge.to, start_direction) {
-                            let p1: Point<i32> = nodes[current_node_id].position.into();
-                            let p2: Point<i32> = nodes[edge.to].position.into();
-                            let new_direction = get_direction(p1, p2);
-                            if let Some(ShortestPathResult { shortest_path_cost: cost_from_node, shortest_path: new_path }) = shortest_path(nodes, edge.to, end, new_direction) {
-                                let total_cost = cost_from_start + cost_from_node;
-                                if total_cost <= shortest_path_cost {
-                                    new_path.iter().copied().for_each(|e| { path_nodes.insert(e); });
-                                    path_nodes.insert(Edge {from: current_node_id, to: edge.to });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-
-            prev_node_id = Some(current_node_id);
-        }
-    }
+//Debug display of the map, best path tiles are O and nodes are + (red if they were pruned)
+fn render_map(grid: &Grid<char>, graph: &CorridorGraph, tiles: &HashSet<Point<i32>>) -> String {
+    grid.iter()
+        .map(|(pos, &c)| {
+            let cell = match graph.node_at(pos) {
+                Some(id) if c == '.' && graph.nodes[id].pruned => "+".red().to_string(),
+                Some(_) if c == '.' => "+".to_string(),
+                _ if tiles.contains(&pos) && c == '.' => "O".to_string(),
+                _ => c.to_string(),
+            };
 
-    path_nodes
+            if pos.x as usize == grid.width() - 1 { format!("{cell}\n") } else { cell }
+        })
+        .collect()
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 16 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day16.txt");
-
-    println!("Reading input from {}", input_file.display());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-    let mut map: Vec<Vec<i32>> = vec![];
+    const EXAMPLE: &str = include_str!("../input/day16-test.txt");
 
-    for line in reader.lines() {
-        let line = line?;
+    #[test]
+    fn part1_example() {
+        assert_eq!(11048, solve_part1(EXAMPLE).unwrap());
+    }
 
-        let row: Vec<i32> = line.chars().map(|c| match c {
-            '#' => -2,  //Wall
-            'S' => -3,  //Start location
-            'E' => -4,  //End location
-            _ => -1,    //Empty space
-        }).collect();
-        map.push(row);
+    #[test]
+    fn part2_example() {
+        assert_eq!(64, solve_part2(EXAMPLE).unwrap());
     }
 
-    let mut nodes: Vec<Node> = vec![];
+    #[test]
+    fn turning_around_costs_two_turns() {
+        //Facing East at the start, the only way out is West
+        assert_eq!(2001, solve_part1("#####\n#ES.#\n#####").unwrap());
+    }
 
-    let (start, end) = find_nodes(&mut map, &mut nodes);
+    #[test]
+    fn walled_off_end_is_an_error() {
+        let maze = "#####\n#S#E#\n#####";
 
-    //Start facing East
-    let start_direction = Point::new(1, 0);
-    let distinct_paths = find_optimal_paths(&nodes, start, end, start_direction);
-
-    //Plot paths on map and count the path tiles - keeping it simplez ;-)
-    for edge in distinct_paths {
-        let p1: Point<i32> = nodes[edge.from].position.into();
-        let p2: Point<i32> = nodes[edge.to].position.into();
-        let direction = get_direction(p1, p2);
-        let mut pos = p1;
-        
-        loop {
-            map[pos.y as usize][pos.x as usize] = -5;
-            if pos == p2 {
-                break;
-            }
-            pos += direction;
-        }
+        assert!(solve_part1(maze).is_err());
+        assert!(solve_part2(maze).is_err());
     }
-
-    let tile_count = map.iter().flatten().filter(|&i| *i == -5).count();
-
-    /*
-    //Debug display of map
-    use colored::Colorize;
-
-    map.iter().for_each(|row| { 
-        row.iter().for_each(|col| {
-            print!("{}", match col {
-                -2 => "##".into(),
-                -3 => "SS".into(),
-                -4 => "EE".into(),
-                -5 => "O ".into(),
-                -1 => ". ".into(),
-                _ => { 
-                    if nodes[*col as usize].is_valid {
-                        format!("{:>2}", col)
-                    } else {
-                        format!("{:>2}", col.to_string().red())
-                    }
-                }
-            });
-        });
-        println!();
-    });
-    */
-    Ok(tile_count as i64)
-}
\ No newline at end of file
+}
@@ -1,17 +1,34 @@
 //https://adventofcode.com/2024/day/17
 
-use std::collections::HashSet;
+use crate::{debug, info, log, trace, warn};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 
-struct ComputerState {
+/// The registers and memory of the 3-bit computer, ready to run from the first instruction.
+pub struct ComputerState {
     register_a: usize,
     register_b: usize,
     register_c: usize,
     ip: usize,
     memory: Vec<u8>,
-    debug_flag: bool,
 }
 
-fn run_program(state: ComputerState) -> Vec<u8> {
+impl ComputerState {
+    /// A computer holding the given register values and program, with the instruction pointer at 0.
+    ///
+    /// ```
+    /// use adventofcode_24::day_17::{assemble, run_program, ComputerState};
+    ///
+    /// let memory = assemble("loop: adv 1\n      out A\n      jnz loop").unwrap();
+    /// assert_eq!(run_program(ComputerState::new(729, 0, 0, memory)), vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+    /// ```
+    pub fn new(register_a: usize, register_b: usize, register_c: usize, memory: Vec<u8>) -> ComputerState {
+        ComputerState { register_a, register_b, register_c, ip: 0, memory }
+    }
+}
+
+/// Runs the program until it halts, returning everything it output.
+pub fn run_program(state: ComputerState) -> Vec<u8> {
     let mut output: Vec<u8> = vec![];
 
     let ComputerState { 
@@ -20,7 +37,6 @@ fn run_program(state: ComputerState) -> Vec<u8> {
         mut register_c, 
         mut ip, 
         memory,
-        debug_flag,
     } = state;
 
     let mut jump_count = 0;
@@ -33,12 +49,15 @@ fn run_program(state: ComputerState) -> Vec<u8> {
         if jump_count > 10 {
             //Deadlock protection!
             //if we get more than 10 consecutive jumps we're probably stuck in a loop!
-            println!("Deadlock detected - halting program");
+            warn!("Deadlock detected - halting program");
             break;
         }
 
         let op_code = memory[ip];
-        let operand = memory[ip+1];
+        //An opcode at the very end of memory has no operand to read, so the program halts
+        let Some(&operand) = memory.get(ip + 1) else {
+            break;
+        };
         //Amount to increment the instruction pointer by, this can be changed by the jnz instruction
         let mut inc: usize = 2;
 
@@ -50,10 +69,7 @@ fn run_program(state: ComputerState) -> Vec<u8> {
             _ => 7, //*** Reserved ***
         };
 
-        if debug_flag {
-            println!("IP:{ip} A:{register_a} B:{register_b} ({}) C:{register_c}", register_b % 8);
-            //println!("OC:{op_code} OP:{operand} COP: {combo_operand} JC: {jump_count}");
-        }
+        trace!("IP:{ip} A:{register_a} B:{register_b} ({}) C:{register_c} OC:{op_code} OP:{operand} COP:{combo_operand} JC:{jump_count}", register_b % 8);
 
         match op_code {
             0 => {
@@ -102,17 +118,12 @@ fn run_program(state: ComputerState) -> Vec<u8> {
         ip += inc;
     }
 
-    if debug_flag {
-        println!("HALTING... Final state:");
-        println!("IP:{ip} A:{register_a} B:{register_b} C:{register_c}");
-    }
+    trace!("HALTING... Final state: IP:{ip} A:{register_a} B:{register_b} C:{register_c}");
 
     output
 }
 
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 17 - Part 1:");
-    
+pub fn parse_program(input: &str) -> anyhow::Result<ComputerState> {
     /*
     Register A: 59397658
     Register B: 0
@@ -121,40 +132,314 @@ pub fn do_part1() -> anyhow::Result<i64> {
     Program: 2,4,1,1,7,5,4,6,1,4,0,3,5,5,3,0
     */
 
-    let _test_program_state = ComputerState {
-        register_a: 729,
+    let mut state = ComputerState {
+        register_a: 0,
         register_b: 0,
         register_c: 0,
-        memory: vec![0,1,5,4,3,0],
+        memory: vec![],
         ip: 0,
-        debug_flag: false,
     };
 
-    let state = ComputerState {
-        register_a: 59397658,
-        register_b: 0,
-        register_c: 0,
-        memory: vec![2,4,1,1,7,5,4,6,1,4,0,3,5,5,3,0],
-        ip: 0,
-        debug_flag: false,
-    };
+    for line in input.lines() {
+        if let Some((name, value)) = line.split_once(": ") {
+            match name {
+                "Register A" => state.register_a = value.trim().parse()?,
+                "Register B" => state.register_b = value.trim().parse()?,
+                "Register C" => state.register_c = value.trim().parse()?,
+                "Program" => state.memory = value.trim()
+                    .split(',')
+                    .map(|i| i.parse::<u8>())
+                    .collect::<Result<Vec<u8>, _>>()?,
+                _ => { /* Ignored */ },
+            }
+        }
+    }
 
-    let output: Vec<u8> = run_program(state);
-    
-    if !output.is_empty() {
-        let out: String = output.iter()
-            .map(|i| i.to_string())
-            .reduce(|curr, nxt| curr + "," + &nxt).unwrap();
-        println!("{out}");
+    Ok(state)
+}
+
+/// An operand that names a value: 0-3 are taken literally, 4-6 read registers A, B and C.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Combo {
+    Literal(u8),
+    A,
+    B,
+    C,
+    /// 7 is reserved and won't appear in valid programs.
+    Reserved,
+}
+
+impl Combo {
+    fn from_operand(operand: u8) -> Combo {
+        match operand {
+            0..=3 => Combo::Literal(operand),
+            4 => Combo::A,
+            5 => Combo::B,
+            6 => Combo::C,
+            _ => Combo::Reserved,
+        }
     }
 
-    Ok(0)
+    fn operand(self) -> u8 {
+        match self {
+            Combo::Literal(value) => value,
+            Combo::A => 4,
+            Combo::B => 5,
+            Combo::C => 6,
+            Combo::Reserved => 7,
+        }
+    }
+}
+
+impl Display for Combo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Combo::Literal(value) => write!(f, "{value}"),
+            Combo::A => write!(f, "A"),
+            Combo::B => write!(f, "B"),
+            Combo::C => write!(f, "C"),
+            Combo::Reserved => write!(f, "7"),
+        }
+    }
+}
+
+/// One decoded instruction, holding either a literal operand or a [`Combo`] one depending on the opcode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    /// A = A / 2^combo
+    Adv(Combo),
+    /// B = B xor literal
+    Bxl(u8),
+    /// B = combo mod 8
+    Bst(Combo),
+    /// Jump to the literal address if A isn't 0
+    Jnz(u8),
+    /// B = B xor C, the operand is read but ignored
+    Bxc(u8),
+    /// Output combo mod 8
+    Out(Combo),
+    /// B = A / 2^combo
+    Bdv(Combo),
+    /// C = A / 2^combo
+    Cdv(Combo),
+}
+
+impl Instruction {
+    /// Decodes an opcode and its operand, `None` if the opcode isn't 0-7.
+    pub fn decode(op_code: u8, operand: u8) -> Option<Instruction> {
+        let combo = Combo::from_operand(operand);
+
+        Some(match op_code {
+            0 => Instruction::Adv(combo),
+            1 => Instruction::Bxl(operand),
+            2 => Instruction::Bst(combo),
+            3 => Instruction::Jnz(operand),
+            4 => Instruction::Bxc(operand),
+            5 => Instruction::Out(combo),
+            6 => Instruction::Bdv(combo),
+            7 => Instruction::Cdv(combo),
+            _ => return None,
+        })
+    }
+
+    /// The opcode and operand to store in memory, the reverse of [`Instruction::decode`].
+    pub fn encode(self) -> [u8; 2] {
+        match self {
+            Instruction::Adv(combo) => [0, combo.operand()],
+            Instruction::Bxl(literal) => [1, literal],
+            Instruction::Bst(combo) => [2, combo.operand()],
+            Instruction::Jnz(address) => [3, address],
+            Instruction::Bxc(ignored) => [4, ignored],
+            Instruction::Out(combo) => [5, combo.operand()],
+            Instruction::Bdv(combo) => [6, combo.operand()],
+            Instruction::Cdv(combo) => [7, combo.operand()],
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Adv(combo) => write!(f, "adv {combo}"),
+            Instruction::Bxl(literal) => write!(f, "bxl {literal}"),
+            Instruction::Bst(combo) => write!(f, "bst {combo}"),
+            Instruction::Jnz(address) => write!(f, "jnz {address}"),
+            //The operand does nothing so only show it when it isn't the usual 0
+            Instruction::Bxc(0) => write!(f, "bxc"),
+            Instruction::Bxc(ignored) => write!(f, "bxc {ignored}"),
+            Instruction::Out(combo) => write!(f, "out {combo}"),
+            Instruction::Bdv(combo) => write!(f, "bdv {combo}"),
+            Instruction::Cdv(combo) => write!(f, "cdv {combo}"),
+        }
+    }
+}
+
+/// Decodes a program two values at a time, stopping at an invalid opcode or a missing operand as the computer would halt there.
+///
+/// ```
+/// use adventofcode_24::day_17::{disassemble, Combo, Instruction};
+///
+/// let program = disassemble(&[0, 1, 5, 4, 3, 0]);
+/// assert_eq!(program, vec![Instruction::Adv(Combo::Literal(1)), Instruction::Out(Combo::A), Instruction::Jnz(0)]);
+/// ```
+pub fn disassemble(memory: &[u8]) -> Vec<Instruction> {
+    memory.chunks_exact(2)
+        .map_while(|pair| Instruction::decode(pair[0], pair[1]))
+        .collect()
+}
+
+/// Renders a program one instruction per line with its address, marking the lines jumped to and where each jump goes.
+pub fn listing(program: &[Instruction]) -> String {
+    let targets: HashSet<usize> = program.iter()
+        .filter_map(|instruction| match instruction {
+            Instruction::Jnz(address) => Some(*address as usize),
+            _ => None,
+        })
+        .collect();
+
+    program.iter()
+        .enumerate()
+        .map(|(index, instruction)| {
+            //Each instruction takes up two values of memory
+            let address = index * 2;
+            let marker = if targets.contains(&address) { '>' } else { ' ' };
+
+            match instruction {
+                Instruction::Jnz(target) => format!("{marker}{address:02}: {:<8}-> {target:02}\n", instruction.to_string()),
+                _ => format!("{marker}{address:02}: {instruction}\n"),
+            }
+        })
+        .collect()
+}
+
+//A literal operand, anything that fits in 3 bits
+fn parse_literal(operand: &str) -> Result<u8, String> {
+    match operand.parse::<u8>() {
+        Ok(value) if value < 8 => Ok(value),
+        _ => Err(format!("expected a value from 0 to 7, found '{operand}'")),
+    }
+}
+
+//A combo operand is 0-3, a register name (in either case) or the reserved 7
+fn parse_combo(operand: &str) -> Result<Combo, String> {
+    match operand.to_ascii_uppercase().as_str() {
+        "A" => Ok(Combo::A),
+        "B" => Ok(Combo::B),
+        "C" => Ok(Combo::C),
+        _ => match parse_literal(operand) {
+            Ok(4..=6) => Err(format!("combo operand {operand} reads a register, write it as A, B or C")),
+            Ok(value) => Ok(Combo::from_operand(value)),
+            Err(_) => Err(format!("expected 0 to 3, A, B or C, found '{operand}'")),
+        },
+    }
+}
+
+//A jump target, either an address or a label
+fn parse_address(operand: &str, labels: &HashMap<&str, usize>) -> Result<u8, String> {
+    match labels.get(operand) {
+        Some(&address) if address < 8 => Ok(address as u8),
+        Some(&address) => Err(format!("label '{operand}' is at address {address}, too far to jump to with a 3 bit operand")),
+        None if operand.starts_with(|c: char| c.is_ascii_digit()) => parse_literal(operand),
+        None => Err(format!("unknown label '{operand}'")),
+    }
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 17 - Part 2:");
+fn parse_instruction(mnemonic: &str, operand: Option<&str>, labels: &HashMap<&str, usize>) -> Result<Instruction, String> {
+    //bxc ignores its operand so it's optional
+    if mnemonic == "bxc" {
+        return Ok(Instruction::Bxc(operand.map(parse_literal).transpose()?.unwrap_or(0)));
+    }
+
+    let operand = operand.ok_or_else(|| format!("'{mnemonic}' needs an operand"))?;
+
+    Ok(match mnemonic {
+        "adv" => Instruction::Adv(parse_combo(operand)?),
+        "bxl" => Instruction::Bxl(parse_literal(operand)?),
+        "bst" => Instruction::Bst(parse_combo(operand)?),
+        "jnz" => Instruction::Jnz(parse_address(operand, labels)?),
+        "out" => Instruction::Out(parse_combo(operand)?),
+        "bdv" => Instruction::Bdv(parse_combo(operand)?),
+        "cdv" => Instruction::Cdv(parse_combo(operand)?),
+        _ => return Err(format!("unknown instruction '{mnemonic}'")),
+    })
+}
+
+/// Assembles mnemonic source into a memory image, the reverse of [`disassemble`].
+///
+/// Each line holds one instruction such as `adv 1`, `out A` or `jnz 0`, written as [`Instruction`] displays them.
+/// A line can start with a `label:` to jump to with `jnz label`, and anything after a `;` is a comment.
+/// Errors give the line number they were found on.
+///
+/// ```
+/// use adventofcode_24::day_17::assemble;
+///
+/// let memory = assemble("start: adv 1\n       out A ; print A mod 8\n       jnz start").unwrap();
+/// assert_eq!(memory, vec![0, 1, 5, 4, 3, 0]);
+/// ```
+pub fn assemble(source: &str) -> anyhow::Result<Vec<u8>> {
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut statements = vec![];
+
+    //Find every label's address first, so jumps can go forwards as well as back
+    for (line_number, line) in source.lines().enumerate().map(|(i, line)| (i + 1, line)) {
+        let mut code = line.split(';').next().unwrap_or_default().trim();
+
+        if let Some((label, rest)) = code.split_once(':') {
+            let label = label.trim();
+            let valid = label.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                anyhow::bail!("line {line_number}: invalid label '{label}'");
+            }
+            //Each instruction takes up two values of memory
+            if labels.insert(label, statements.len() * 2).is_some() {
+                anyhow::bail!("line {line_number}: label '{label}' is already defined");
+            }
+            code = rest.trim();
+        }
+
+        if !code.is_empty() {
+            statements.push((line_number, code));
+        }
+    }
+
+    let mut memory = vec![];
+    for (line_number, code) in statements {
+        let mut parts = code.split_whitespace();
+        let mnemonic = parts.next().unwrap_or_default().to_ascii_lowercase();
+        let operand = parts.next();
+
+        if let Some(extra) = parts.next() {
+            anyhow::bail!("line {line_number}: unexpected '{extra}' after the operand");
+        }
 
-    let target = [2,4,1,1,7,5,4,6,1,4,0,3,5,5,3,0];
+        let instruction = parse_instruction(&mnemonic, operand, &labels)
+            .map_err(|message| anyhow::anyhow!("line {line_number}: {message}"))?;
+        memory.extend(instruction.encode());
+    }
+
+    Ok(memory)
+}
+
+pub fn solve_part1(input: &str) -> anyhow::Result<String> {
+    let state = parse_program(input)?;
+
+    if log::enabled(log::Level::Debug) {
+        debug!("Program:\n{}", listing(&disassemble(&state.memory)));
+    }
+
+    let output: Vec<u8> = run_program(state);
+
+    //The answer is the program's output joined with commas
+    Ok(output.iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<String>>()
+        .join(","))
+}
+
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    //The program should output a copy of itself
+    let target = parse_program(input)?.memory;
     
     //This is brute force approach and will run for a long time but gets to the answer within 3-5mins
     //202366627359274.25
@@ -241,7 +526,7 @@ fn brute_force(output: &[u8]) -> Option<usize> {
 
             if test_output.len() <= output.len() && test_output[..] == output[test_index..] {
                 count += 1;
-                //println!("{}/{} a:{a} new a:{new_a} -> {:?}<>{:?}", test_output.len(), output.len(), test_output, &output[test_index..]);
+                trace!("{}/{} a:{a} new a:{new_a} -> {:?}<>{:?}", test_output.len(), output.len(), test_output, &output[test_index..]);
                 if test_output == output {
                     //it all matched!
                     matched.insert(new_a as usize);
@@ -250,11 +535,11 @@ fn brute_force(output: &[u8]) -> Option<usize> {
                     if let Some(curr_value) = lowest_match_found {
                         if curr_value > new_a as usize {
                             lowest_match_found = Some(new_a as usize);
-                            //Print it out so we don't have to wait an hour for the result!
-                            println!("{new_a} ({next_to_match})");
+                            //Log it so we don't have to wait an hour for the result!
+                            info!("New lowest match {new_a} ({next_to_match})");
                         }
                     } else {
-                        println!("{new_a}");
+                        info!("First match {new_a}");
                         lowest_match_found = Some(new_a as usize);
                     }
 
@@ -280,7 +565,111 @@ fn brute_force(output: &[u8]) -> Option<usize> {
         test_values.remove(&(a_str, next_to_match));
     }
 
-    println!("Matches: {count} Tested: {tested}");
+    debug!("Matches: {count} Tested: {tested}");
 
     lowest_match_found
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day17-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!("4,6,3,5,6,3,5,2,1,0", solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn disassemble_operands() {
+        let program = disassemble(&[2, 4, 1, 1, 7, 5, 4, 6, 0, 3, 5, 5, 3, 0]);
+
+        assert_eq!(program, vec![
+            Instruction::Bst(Combo::A),
+            Instruction::Bxl(1),
+            Instruction::Cdv(Combo::B),
+            Instruction::Bxc(6),
+            Instruction::Adv(Combo::Literal(3)),
+            Instruction::Out(Combo::B),
+            Instruction::Jnz(0),
+        ]);
+    }
+
+    #[test]
+    fn missing_operand_halts() {
+        assert_eq!(run_program(ComputerState::new(13, 0, 0, vec![5, 4, 3])), vec![5]);
+    }
+
+    #[test]
+    fn disassemble_stops_at_a_missing_operand() {
+        assert_eq!(disassemble(&[5, 4, 3]), vec![Instruction::Out(Combo::A)]);
+        assert_eq!(disassemble(&[5, 4, 8, 0, 5, 4]), vec![Instruction::Out(Combo::A)]);
+    }
+
+    #[test]
+    fn assemble_example() {
+        let source = "\
+; Halves A and outputs it until it reaches 0
+loop:
+    adv 1
+    out A
+    jnz loop";
+
+        let memory = assemble(source).unwrap();
+        assert_eq!(memory, parse_program(EXAMPLE).unwrap().memory);
+
+        assert_eq!(run_program(ComputerState::new(729, 0, 0, memory)), vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+    }
+
+    #[test]
+    fn assemble_forward_jumps_and_registers() {
+        let memory = assemble("jnz end\nbst C\nend: bdv B\nbxc\nbxc 3\ncdv 7").unwrap();
+        assert_eq!(memory, vec![3, 4, 2, 6, 6, 5, 4, 0, 4, 3, 7, 7]);
+
+        //Mnemonics and register names can be in any case
+        assert_eq!(assemble("OUT a\nBst c").unwrap(), vec![5, 4, 2, 6]);
+    }
+
+    #[test]
+    fn assembler_round_trips() {
+        //Every opcode with every operand that decodes to something
+        for op_code in 0..8 {
+            for operand in 0..8 {
+                let memory = vec![op_code, operand];
+                let source: Vec<String> = disassemble(&memory).iter().map(Instruction::to_string).collect();
+
+                assert_eq!(assemble(&source.join("\n")).unwrap(), memory, "{source:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn assembler_errors_give_the_line() {
+        let error = |source: &str| assemble(source).unwrap_err().to_string();
+
+        assert_eq!(error("adv 1\nmul 2"), "line 2: unknown instruction 'mul'");
+        assert_eq!(error("adv 1\n\nout 5"), "line 3: combo operand 5 reads a register, write it as A, B or C");
+        assert_eq!(error("bxl 8"), "line 1: expected a value from 0 to 7, found '8'");
+        assert_eq!(error("out"), "line 1: 'out' needs an operand");
+        assert_eq!(error("out A B"), "line 1: unexpected 'B' after the operand");
+        assert_eq!(error("jnz nowhere"), "line 1: unknown label 'nowhere'");
+        assert_eq!(error("a: out A\na: out B"), "line 2: label 'a' is already defined");
+        assert_eq!(error("1st: out A"), "line 1: invalid label '1st'");
+        assert_eq!(
+            error(&format!("{}far: jnz far", "out A\n".repeat(4))),
+            "line 5: label 'far' is at address 8, too far to jump to with a 3 bit operand",
+        );
+    }
+
+    #[test]
+    fn listing_shows_addresses_and_jumps() {
+        let program = parse_program(EXAMPLE).unwrap();
+
+        assert_eq!(listing(&disassemble(&program.memory)), "\
+>00: adv 1
+ 02: out A
+ 04: jnz 0   -> 00
+");
+    }
+}
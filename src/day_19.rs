@@ -1,10 +1,29 @@
 //https://adventofcode.com/2024/day/19
 
-use std::{collections::{HashMap, HashSet}, fs::File, io::{BufRead, BufReader}};
-
-//Non-Deterministic Finite Automata
+use std::collections::{HashMap, HashSet};
+
+/// A non-deterministic finite automaton that recognises any sequence made by joining
+/// its patterns end to end, such as towels in a row of stripes.
+///
+/// Patterns are added with [`Nfa::recognise_states`], then a sequence is fed in with
+/// [`Nfa::apply_states`] and checked with [`Nfa::is_acceptable`].
+///
+/// ```
+/// use adventofcode_24::day_19::Nfa;
+///
+/// let mut nfa: Nfa<char> = Nfa::new();
+/// nfa.recognise_states(&['r']);
+/// nfa.recognise_states(&['w', 'r']);
+///
+/// nfa.apply_states(&['r', 'w', 'r']);
+/// assert!(nfa.is_acceptable());
+///
+/// nfa.reset();
+/// nfa.apply_states(&['w', 'w']);
+/// assert!(!nfa.is_acceptable());
+/// ```
 #[derive(Debug)]
-struct Nfa<T> {
+pub struct Nfa<T> {
     initial_state: usize,
     accept_state: usize,
     transitions: HashMap<(usize, Option<T>), HashSet<usize>>,
@@ -16,7 +35,8 @@ struct Nfa<T> {
 impl<T> Nfa<T> 
     where T : Clone + Copy + Eq + std::hash::Hash + std::fmt::Debug
 {
-    fn new() -> Nfa<T> {
+    /// An automaton with only its initial and accept states, it recognises nothing until patterns are added.
+    pub fn new() -> Nfa<T> {
         let initial_state = 0;
         let accept_state = 1;
         let mut current_states = HashSet::new();
@@ -37,13 +57,15 @@ impl<T> Nfa<T>
         }
     }
 
-    fn reset(&mut self) {
+    /// Go back to the initial state ready for another sequence, the recognised patterns are kept.
+    pub fn reset(&mut self) {
         self.current_states.clear();
         self.current_states.insert(self.initial_state);
         self.accepted_state_hit_count = 0;
     }
 
-    fn apply_state(&mut self, new_state: T) {
+    /// Feed the next value of the sequence in.
+    pub fn apply_state(&mut self, new_state: T) {
         let mut new_current_states: HashSet<usize> = HashSet::new();
 
         for index in &self.current_states {
@@ -64,16 +86,24 @@ impl<T> Nfa<T>
         }
     }
 
-    fn apply_states(&mut self, new_states: &[T]) {
+    /// Feed each value of a sequence in, in order.
+    pub fn apply_states(&mut self, new_states: &[T]) {
         new_states.iter().for_each(|state| self.apply_state(*state));
     }
 
-    fn is_acceptable(&self) -> bool {
+    /// Whether the values applied since the last reset are a whole number of patterns.
+    pub fn is_acceptable(&self) -> bool {
         let accept_set = HashSet::from([self.accept_state]);
         self.current_states.intersection(&accept_set).any(|_| true)
     }
 
-    fn recognise_states(&mut self, valid_transitions: &[T]) {
+    /// Add a pattern, sharing states with the patterns already added where their prefixes match.
+    /// An empty pattern is ignored.
+    pub fn recognise_states(&mut self, valid_transitions: &[T]) {
+        if valid_transitions.is_empty() {
+            return;
+        }
+
         let mut current_index = self.initial_state;
 
         for i in 0..valid_transitions.len() - 1 {
@@ -111,31 +141,36 @@ impl<T> Nfa<T>
     }
 }
 
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 19 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day19.txt");
-
-    println!("Reading input from {}", input_file.display());
+impl<T> Default for Nfa<T>
+    where T : Clone + Copy + Eq + std::hash::Hash + std::fmt::Debug
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
+//The towel patterns available, and the designs to make from them
+pub fn parse_towels(input: &str) -> anyhow::Result<(Vec<String>, Vec<String>)> {
     let mut is_first_line = true;
     
     let mut valid_patterns: Vec<String> = vec![];
     let mut patterns_to_test: Vec<String> = vec![];
 
-    for line in reader.lines() {
-        let line = line?;
+    for line in input.lines() {
         if is_first_line {
             valid_patterns = line.split(", ").map(|s| s.to_string()).collect();
             is_first_line = false;
         } else if !line.is_empty() {
-            patterns_to_test.push(line);
+            patterns_to_test.push(line.to_string());
         }
     }
 
+    Ok((valid_patterns, patterns_to_test))
+}
+
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
+    let (mut valid_patterns, patterns_to_test) = parse_towels(input)?;
+
     //Use a non-deterministic finite automata to test the patterns are valid
     let mut nfa: Nfa<char> = Nfa::new();
     valid_patterns.sort_by_key(|a| a.len());
@@ -187,30 +222,8 @@ fn calculate_combinations<'a>(pattern: &'a str, valid_patterns: &Vec<&str>, hist
     }
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 19 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day19.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-    let mut is_first_line = true;
-    
-    let mut valid_patterns: Vec<String> = vec![];
-    let mut patterns_to_test: Vec<String> = vec![];
-
-    for line in reader.lines() {
-        let line = line?;
-        if is_first_line {
-            valid_patterns = line.split(", ").map(|s| s.to_string()).collect();
-            is_first_line = false;
-        } else if !line.is_empty() {
-            patterns_to_test.push(line);
-        }
-    }
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    let (valid_patterns, patterns_to_test) = parse_towels(input)?;
 
     let mut total_combinations = 0;
 
@@ -388,4 +401,16 @@ mod tests {
         nfa.apply_states(&"bbrgwb".chars().collect::<Vec<char>>());
         assert!(!nfa.is_acceptable());
     }
-}
\ No newline at end of file
+
+    const EXAMPLE: &str = include_str!("../input/day19-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(6, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(16, solve_part2(EXAMPLE).unwrap());
+    }
+}
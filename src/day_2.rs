@@ -1,6 +1,22 @@
 //https://adventofcode.com/2024/day/2
 
-use std::{fs::File, io::{BufRead, BufReader}};
+use crate::trace;
+
+pub fn parse_reports(input: &str) -> anyhow::Result<Vec<Vec<i32>>> {
+    let mut reports = Vec::new();
+
+    for report in input.lines() {
+        //With each report split the line into readings
+        let readings = report
+            .split(" ")
+            .map(|r| r.parse::<i32>())
+            .collect::<Result<Vec<i32>, _>>()?;
+
+        reports.push(readings);
+    }
+
+    Ok(reports)
+}
 
 fn is_safe(readings: &[i32]) -> bool {
     let mut previous_reading = readings[0];
@@ -23,28 +39,10 @@ fn is_safe(readings: &[i32]) -> bool {
     is_safe
 }
 
-pub fn run() -> anyhow::Result<()> {
-    println!("Day 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day2.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
     let mut num_safe_readings = 0;
     
-    for line in reader.lines() {
-        let report = line?;
-        
-        //With each report split the line into readings
-        let readings: Vec<i32> = report
-            .split(" ")
-            .map(|r| r.parse::<i32>().unwrap())
-            .collect();
-
+    for readings in parse_reports(input)? {
         //Determine whether the reading is safe based on the following rules:
         // - All numbers are either ascending or descending
         // - Distances between numbers are between 1..3 but no more or less
@@ -54,59 +52,62 @@ pub fn run() -> anyhow::Result<()> {
         }
     }
 
-    println!("Part 1 - {num_safe_readings} readings are safe!");
-    
-    //Part 2 - the problem dampener....
-    let file = File::open(input_file)?;
-    let reader = BufReader::new(file);
-
-    let do_debug = false;
+    Ok(num_safe_readings)
+}
 
-    for line in reader.lines() {
-        let report = line?;
-        
-        //With each report split the line into readings
-        let readings: Vec<i32> = report
-            .split(" ")
-            .map(|r| r.parse::<i32>().unwrap())
-            .collect();
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    //Part 2 - the problem dampener....
+    let mut num_safe_readings = 0;
 
+    for readings in parse_reports(input)? {
         //Determine whether the reading is safe based on the following rules:
         // - All numbers are either ascending or descending
         // - Distances between numbers are between 1..3 but no more or less
         //Problem dampener allows for ONE violation of the above rules
 
         if is_safe(&readings) {
-            //num_safe_readings += 1;
-            if do_debug {
-                print!("Was safe first time. ");
-            }
+            num_safe_readings += 1;
+            trace!("Readings: {readings:?} were safe first time");
         }
         else {
             //Readings weren't safe; Apply the problem dampener...
             //Try removing an item, if the remaining readings are safe then stop
-            if do_debug {
-                print!("Was not safe first time...");
-            }
+            let mut made_safe = false;
+
             for i in 0..readings.len() {
                 let mut new_readings = readings.clone();
                 new_readings.remove(i);
 
                 if is_safe(&new_readings) {
-                    if do_debug {
-                        print!("removing reading {} ({}) made it safe. ", i+1, readings[i]);
-                    }
+                    trace!("Readings: {readings:?} were not safe first time, removing reading {} ({}) made them safe", i+1, readings[i]);
                     num_safe_readings += 1;
+                    made_safe = true;
                     break;
                 }
             }
-        }
-        if do_debug { 
-            println!("Readings: {:?}", readings);
+
+            if !made_safe {
+                trace!("Readings: {readings:?} were not safe");
+            }
         }
     }
 
-    println!("Part 2 - {num_safe_readings} readings are now also safe!");
+    Ok(num_safe_readings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day2-test.txt");
 
-    Ok(())
-}
\ No newline at end of file
+    #[test]
+    fn part1_example() {
+        assert_eq!(2, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(4, solve_part2(EXAMPLE).unwrap());
+    }
+}
@@ -1,30 +1,23 @@
 //https://adventofcode.com/2024/day/20
 
-use std::{fs::File, io::{BufRead, BufReader}, thread::current};
-use crate::misc_types::{Point, Rect};
+use crate::misc_types::{Direction, Point};
+use crate::{debug, log};
+use anyhow::bail;
 
-pub fn do_part1() -> anyhow::Result<usize> {
-    println!("Day 20 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day20.txt");
+//Cell types, track cells are given their distance from the start once it's walked
+const EMPTY_SPACE: i32 = -1;
+const WALL: i32 = -2;
+const START: i32 = -3;
+const END: i32 = -4;
 
-    println!("Reading input from {}", input_file.display());
+pub type Racetrack = Vec<Vec<i32>>;
 
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
-    //Cell types
-    const EMPTY_SPACE: i32 = -1;
-    const WALL: i32 = -2;
-    const START: i32 = -3;
-    const END: i32 = -4;
-
-    //Load the map
+//The map of cell types, with the start and end positions
+pub fn parse_racetrack(input: &str) -> anyhow::Result<(Racetrack, Point<usize>, Point<usize>)> {
     let mut start_pos: Point<usize> = Point::new(0, 0);
     let mut end_pos: Point<usize> = Point::new(0,0);
-    let mut map: Vec<Vec<i32>> = reader.lines().enumerate().map(|(y, line)| 
-        line.unwrap().chars().enumerate().map(|(x, c)| 
+    let map: Vec<Vec<i32>> = input.lines().enumerate().map(|(y, line)| 
+        line.chars().enumerate().map(|(x, c)| 
             match c {
                 '#' => WALL,
                 'S' => {
@@ -40,52 +33,48 @@ pub fn do_part1() -> anyhow::Result<usize> {
         ).collect()
     ).collect();
 
-    let bounds: Rect = Rect::new(
-        0, 
-        0, 
-        map[0].len() as i32, 
-        map.len() as i32
-    );
-
-    let directions = [
-        Point::new(0, -1),
-        Point::new(1, 0),
-        Point::new(0, 1),
-        Point::new(-1, 0),
-    ];
-    
+    if map.is_empty() {
+        bail!("The racetrack is empty");
+    }
+
+    Ok((map, start_pos, end_pos))
+}
+
+pub fn solve_part1(input: &str) -> anyhow::Result<usize> {
+    count_cheats(input, 100)
+}
+
+//Count the cheats that save at least min_saving picoseconds
+fn count_cheats(input: &str, min_saving: i32) -> anyhow::Result<usize> {
+    let (mut map, start_pos, end_pos) = parse_racetrack(input)?;
+    let (width, height) = (map[0].len(), map.len());
+    let in_bounds = |pos: &Point<usize>| pos.x < width && pos.y < height;
+
     //From the start position, walk the racetrack marking the distance of each step from the start
     //Note any locations where the wall is one cell thick and there's empty space on the otherside
-    let mut current_direction: Point<i32> = directions[0];
+    let mut current_direction = Direction::North;
     let mut current_pos: Point<usize> = start_pos;
     let mut distance = 0;
     let mut cheat_points: Vec<(Point<usize>, Point<usize>)> = vec![];
 
     while current_pos != end_pos {
-        if current_pos != start_pos {
-            map[current_pos.y][current_pos.x] = distance;
-        }
+        map[current_pos.y][current_pos.x] = distance;
 
         let mut next_direction = current_direction;
-        let prev_cell_dir: Point<i32> = Point::new(-current_direction.x, -current_direction.y);
-        directions.iter()
+        let prev_cell_dir = current_direction.opposite();
+        Direction::CARDINAL.iter()
             .filter(|&&d| d != prev_cell_dir)
             .for_each(|&d| {
-                let probe: Point<i32> = d + current_pos.into();
-
-                if bounds.is_inside(probe) {
-                    let u_probe: Point<usize> = probe.into();
-                    match map[u_probe.y][u_probe.x] {
-                        EMPTY_SPACE => next_direction = d,
+                if let Some(probe) = current_pos.offset(d).filter(in_bounds) {
+                    match map[probe.y][probe.x] {
+                        EMPTY_SPACE | END => next_direction = d,
                         WALL => {
-                            let next_cell = probe + d;
                             //If cell beyond wall is in bounds
-                            if next_cell.inside(bounds) {
-                                let u_next_cell: Point<usize> = next_cell.into();
-                                //If cell beyond wall is empty space
-                                if map[u_next_cell.y][u_next_cell.x] == EMPTY_SPACE {
+                            if let Some(next_cell) = probe.offset(d).filter(in_bounds) {
+                                //If cell beyond wall is empty space or the finish line
+                                if matches!(map[next_cell.y][next_cell.x], EMPTY_SPACE | END) {
                                     //Potential cheat point
-                                    cheat_points.push((current_pos, u_next_cell));
+                                    cheat_points.push((current_pos, next_cell));
                                 }
                             }
                         },
@@ -94,62 +83,62 @@ pub fn do_part1() -> anyhow::Result<usize> {
                 }
             });
 
-        let mut next_pos: Point<i32> = current_pos.into();
-        next_pos += next_direction;
-
-        if next_pos.inside(bounds) {
-            current_pos = next_pos.into();
+        if let Some(next_pos) = current_pos.offset(next_direction).filter(in_bounds) {
+            current_pos = next_pos;
             current_direction = next_direction;
             distance += 1;
         } else {
-            println!("Dist:{distance}, pos:{}, next_pos:{} dir:{} next_dir:{}", current_pos, next_pos, current_direction, next_direction);
-            panic!("Left the racetrack and crashed!");
+            bail!("Left the racetrack and crashed! Dist:{distance}, pos:{}, dir:{} next_dir:{}", current_pos, current_direction, next_direction);
         }
     }
 
+    map[end_pos.y][end_pos.x] = distance;
+
+    if log::enabled(log::Level::Debug) {
+        debug!("Racetrack distances:\n{}", render_map(&map));
+    }
+
     //Calculate the distances & rank
     let num_cheats = cheat_points.iter()
         .map(|&(from, to)| map[to.y][to.x] - map[from.y][from.x])
-        .filter(|&distance| distance > 100)
+        //A cheat still spends 2 picoseconds passing through the wall
+        .filter(|&distance| distance - 2 >= min_saving)
         .count();
 
     Ok(num_cheats)
 }
 
-fn print_map(map: &[Vec<i32>]) {
-    map.iter().for_each(|row| {
-        row.iter().for_each(|&column| 
-            {
-                let c_str = if column >= 0 {
-                    column.to_string() 
-                } else {
-                    String::from(".")
-                };
-
-                print!("{}",
-                    match column {
-                        -2 => "#",
-                        -3 => "S",
-                        -4 => "E",
-                        _ => c_str.as_str(),
-                    }
-                );
-            }
-        );
-        println!();
-    });
+//Debug display of the racetrack, each track cell shows its distance from the start
+fn render_map(map: &[Vec<i32>]) -> String {
+    map.iter()
+        .map(|row| row.iter()
+            .map(|&column| match column {
+                -2 => String::from("#"),
+                -3 => String::from("S"),
+                -4 => String::from("E"),
+                _ if column >= 0 => column.to_string(),
+                _ => String::from("."),
+            })
+            .collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 20 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day20.txt");
+pub fn solve_part2(_input: &str) -> anyhow::Result<i64> {
+    Ok(0)
+}
 
-    println!("Reading input from {}", input_file.display());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
+    const EXAMPLE: &str = include_str!("../input/day20-test.txt");
 
-    Ok(0)
-}
\ No newline at end of file
+    #[test]
+    fn part1_example() {
+        assert_eq!(1, count_cheats(EXAMPLE, 64).unwrap());
+        assert_eq!(2, count_cheats(EXAMPLE, 40).unwrap());
+        assert_eq!(5, count_cheats(EXAMPLE, 20).unwrap());
+        assert_eq!(44, count_cheats(EXAMPLE, 1).unwrap());
+    }
+}
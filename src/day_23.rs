@@ -1,31 +1,9 @@
 //https://adventofcode.com/2024/day/23
 
-use std::{fs::File, io::{BufRead, BufReader}};
-
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 23 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day23.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
+pub fn solve_part1(_input: &str) -> anyhow::Result<i64> {
     Ok(0)
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 23 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day23.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
+pub fn solve_part2(_input: &str) -> anyhow::Result<i64> {
     Ok(0)
 }
\ No newline at end of file
@@ -1,7 +1,5 @@
 //https://adventofcode.com/2024/day/3
 
-use std::{fs::File, io::{BufRead, BufReader}};
-
 fn extract_digits(text: &str) -> Option<(i32, i32)> {
     //Look for digit from start of slice to next ','
     if let Some(comma_index) = text.find(',') {
@@ -21,23 +19,12 @@ fn extract_digits(text: &str) -> Option<(i32, i32)> {
     None
 }
 
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 3 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day3.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
     let mut running_total = 0_i64;
 
-    for line in reader.lines() {
-        let line = line?;
+    for line in input.lines() {
         let mut searching = true;
-        let mut remaining = &line[..];
+        let mut remaining = line;
 
         while searching {
             if let Some(mul_index) = remaining.find("mul(") {
@@ -67,24 +54,13 @@ pub fn do_part1() -> anyhow::Result<i64> {
     Ok(running_total)
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 3 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day3.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
     let mut running_total = 0_i64;
     let mut is_doing = true;
 
-    for line in reader.lines() {
-        let line = line?;
+    for line in input.lines() {
         let mut searching_for_blocks = true;
-        let mut remaining = &line[..];
+        let mut remaining = line;
 
         while searching_for_blocks {
 
@@ -143,4 +119,22 @@ pub fn do_part2() -> anyhow::Result<i64> {
     }
 
     Ok(running_total)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day3-test.txt");
+    const EXAMPLE_PART2: &str = include_str!("../input/day3-part2-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(161, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(48, solve_part2(EXAMPLE_PART2).unwrap());
+    }
+}
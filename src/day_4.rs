@@ -1,22 +1,10 @@
 //https://adventofcode.com/2024/day/4
 
-use std::{fs::File, io::{BufRead, BufReader}};
-
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 4 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day4.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
     let mut word_search: Vec<Vec<char>> = Vec::new();
 
-    for line in reader.lines() {
-        word_search.push(line?.chars().collect());
+    for line in input.lines() {
+        word_search.push(line.chars().collect());
     }
 
     // Word              S  S  S
@@ -121,21 +109,11 @@ pub fn do_part1() -> anyhow::Result<i64> {
     Ok(word_count)
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 4 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day4.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
     let mut word_search: Vec<String> = Vec::new();
 
-    for line in reader.lines() {
-        word_search.push(line?.clone());
+    for line in input.lines() {
+        word_search.push(line.to_string());
     }
 
     // Word              
@@ -173,4 +151,21 @@ pub fn do_part2() -> anyhow::Result<i64> {
     }
 
     Ok(mas_count)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day4-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(18, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(9, solve_part2(EXAMPLE).unwrap());
+    }
+}
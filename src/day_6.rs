@@ -1,36 +1,33 @@
 //https://adventofcode.com/2024/day/6
 
-use std::{collections::HashSet, fs::File, io::{BufRead, BufReader}};
-
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 6 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day6.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
-    let directions:[(i32, i32); 4] = [ 
-        (0, -1), //UP
-        (1, 0),  //RIGHT
-        (0, 1),  //DOWN
-        (-1, 0)  //LEFT
-    ];
-    let mut curr_direction: usize = 0;
-    let mut curr_position: (usize, usize) = (0, 0);
+use crate::misc_types::Direction;
+use crate::{debug, log};
+use std::collections::HashSet;
+use anyhow::anyhow;
+
+//Each row of the lab's map
+pub type LabMap = Vec<Vec<char>>;
+
+//The map of the lab, and where the guard starts
+pub fn parse_lab(input: &str) -> anyhow::Result<(LabMap, (usize, usize))> {
+    let mut guard_position = None;
     let mut map: Vec<Vec<char>> = Vec::new();
 
-    for (i, line) in reader.lines().enumerate() {
-        let line = line?;
+    for (i, line) in input.lines().enumerate() {
         map.push(line.as_bytes().iter().map(|b| *b as char).collect());
         if let Some(index) = line.find('^') {
-            curr_position = (index, i);
+            guard_position = Some((index, i));
         }
     }
 
+    let guard_position = guard_position.ok_or_else(|| anyhow!("No guard (^) on the map"))?;
+    Ok((map, guard_position))
+}
+
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
+    let mut curr_direction = Direction::North;
+    let (mut map, mut curr_position) = parse_lab(input)?;
+
     let width = map[0].len();
     let height = map.len();
 
@@ -38,15 +35,12 @@ pub fn do_part1() -> anyhow::Result<i64> {
     let mut steps = 1;
     map[curr_position.1][curr_position.0] = 'v';
 
-    while let Some((x, y)) = add(curr_position, directions[curr_direction]) {
+    while let Some((x, y)) = add(curr_position, curr_direction) {
         //Check we are still in bounds
         if x < width && y < height {
             //If we hit something then change direction
             if map[y][x] == '#' {
-                curr_direction += 1;
-                if curr_direction > 3 {
-                    curr_direction = 0;
-                }
+                curr_direction = curr_direction.turn_right();
             } else {
                 //Move
                 if map[y][x] != 'v' {
@@ -62,20 +56,16 @@ pub fn do_part1() -> anyhow::Result<i64> {
         }
     }
 
-    if false /* display map */ {
-        for row in map {
-            for c in row {
-                print!("{c}");
-            }
-            println!();
-        }
+    if log::enabled(log::Level::Debug) {
+        debug!("Guard's route:\n{}", render_map(&map));
     }
 
     Ok(steps as i64)
 }
 
-fn add(pos: (usize, usize), offset: (i32, i32)) -> Option<(usize, usize)> {
-    let new_pos = (pos.0 as i32 + offset.0, pos.1 as i32 + offset.1);
+fn add(pos: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+    let offset = direction.to_point();
+    let new_pos = (pos.0 as i32 + offset.x, pos.1 as i32 + offset.y);
 
     if new_pos.0 >= 0 && new_pos.1 >= 0 {
         return Some((new_pos.0 as usize, new_pos.1 as usize))
@@ -84,23 +74,21 @@ fn add(pos: (usize, usize), offset: (i32, i32)) -> Option<(usize, usize)> {
     None
 }
 
-fn display_direction(direction: usize) -> char {
+fn display_direction(direction: Direction) -> char {
     match direction {
-        0 => '^',
-        1 => '>',
-        2 => 'v',
-        3 => '<',
-        _ => { panic!("Whoops! {direction}")},
+        Direction::North => '^',
+        Direction::East => '>',
+        Direction::South => 'v',
+        Direction::West => '<',
+        _ => { panic!("The guard only turns right, not {direction}")},
     }
 }
 
-fn display_map(map: &Vec<Vec<char>>) {
-    for row in map {
-        for c in row {
-            print!("{c}");
-        }
-        println!();
-    }
+fn render_map(map: &[Vec<char>]) -> String {
+    map.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
 fn add_obstacles(map: &mut [Vec<char>], obstacle_positions: &HashSet<(usize, usize)>) {
@@ -109,34 +97,9 @@ fn add_obstacles(map: &mut [Vec<char>], obstacle_positions: &HashSet<(usize, usi
     }
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 6 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day6.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
-    let directions:[(i32, i32); 4] = [ 
-        (0, -1), //UP
-        (1, 0),  //RIGHT
-        (0, 1),  //DOWN
-        (-1, 0)  //LEFT
-    ];
-    let mut curr_direction: usize = 0;
-    let mut curr_position: (usize, usize) = (0, 0);
-    let mut map: Vec<Vec<char>> = Vec::new();
-
-    for (i, line) in reader.lines().enumerate() {
-        let line = line?;
-        map.push(line.as_bytes().iter().map(|b| *b as char).collect());
-        if let Some(index) = line.find('^') {
-            curr_position = (index, i);
-        }
-    }
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    let mut curr_direction = Direction::North;
+    let (mut map, mut curr_position) = parse_lab(input)?;
 
     let width = map[0].len();
     let height = map.len();
@@ -146,15 +109,12 @@ pub fn do_part2() -> anyhow::Result<i64> {
     let mut changed_direction = false;
     let mut obstacle_positions: HashSet<(usize, usize)> = HashSet::new();
 
-    while let Some((x, y)) = add(curr_position, directions[curr_direction]) {
+    while let Some((x, y)) = add(curr_position, curr_direction) {
         //Check we are still in bounds
         if x < width && y < height {
             //If we hit something then change direction
             if map[y][x] == '#' {
-                curr_direction += 1;
-                if curr_direction > 3 {
-                    curr_direction = 0;
-                }
+                curr_direction = curr_direction.turn_right();
                 changed_direction = true;
             } else {
                 //Try and trap the guard by adding an obstacle in the position we're about to move to
@@ -166,15 +126,12 @@ pub fn do_part2() -> anyhow::Result<i64> {
                 let mut target_position = curr_position;
                 let mut target_direction = curr_direction;
                 let mut wall_collisions: HashSet<((usize, usize), (usize, usize))> = HashSet::new();
-                while let Some((x, y)) = add(target_position, directions[target_direction]) {
+                while let Some((x, y)) = add(target_position, target_direction) {
                     //Check we are still in bounds
                     if x < width && y < height {
                         //If we hit something then change direction
                         if map[y][x] == '#' {
-                            target_direction += 1;
-                            if target_direction > 3 {
-                                target_direction = 0;
-                            }
+                            target_direction = target_direction.turn_right();
                             //Record current position and wall position
                             let wall_collision = (target_position, (x, y));
                             if wall_collisions.contains(&wall_collision) {
@@ -208,7 +165,26 @@ pub fn do_part2() -> anyhow::Result<i64> {
     }
 
     add_obstacles(&mut map, &obstacle_positions);
-    display_map(&map);
+    if log::enabled(log::Level::Debug) {
+        debug!("Guard's route with the new obstacles:\n{}", render_map(&map));
+    }
 
     Ok(obstacle_positions.len() as i64)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day6-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(41, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(6, solve_part2(EXAMPLE).unwrap());
+    }
+}
@@ -1,132 +1,115 @@
 //https://adventofcode.com/2024/day/7
 
-use std::{fs::File, io::{BufRead, BufReader}};
+use crate::trace;
 
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 7 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day7.txt");
+pub fn parse_equations(input: &str) -> anyhow::Result<Vec<(i64, Vec<i64>)>> {
+    let mut equations = Vec::new();
 
-    println!("Reading input from {}", input_file.display());
+    for line in input.lines() {
+        if let Some((test_value, number_str)) = line.split_once(": ") {
+            let test_value: i64 = test_value.parse()?;
+            let numbers = number_str.split(" ")
+                .map(|s| s.parse::<i64>())
+                .collect::<Result<Vec<i64>, _>>()?;
 
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
+            equations.push((test_value, numbers));
+        }
+    }
 
-    let mut total_calibration_result = 0;
-    for line in reader.lines() {
-        let line = line?;
+    Ok(equations)
+}
 
-        if let Some((test_value, number_str)) = line.split_once(": ") {
-            let test_value: i64 = test_value.parse()?;
-            let numbers: Vec<i64> = number_str.split(" ").map(|s| s.parse().unwrap()).collect();
-
-            let mut pass = false;
-            let iterations = 2_i32.pow(numbers.len() as u32 - 1);
-
-            for i in 0..iterations {
-
-                let mut result = numbers[0];
-                let mut equation = numbers[0].to_string();
-
-                for (num_index, number) in numbers.iter().enumerate().skip(1) {
-                    let do_addition = 1 << (num_index-1) & i != 0;
-
-                    result = if do_addition { 
-                        equation = format!("{} + {}", equation, *number);
-                        result + *number
-                    } else { 
-                        equation = format!("{} * {}", equation, *number);
-                        if *number == 1 {
-                            result
-                        } else {
-                            result * *number
-                        }
-                    };
-
-                     //Short circuit
-                     if result > test_value {
-                        break;
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
+    let mut total_calibration_result = 0;
+    for (test_value, numbers) in parse_equations(input)? {
+        let mut pass = false;
+        let iterations = 2_i32.pow(numbers.len() as u32 - 1);
+
+        for i in 0..iterations {
+
+            let mut result = numbers[0];
+            let mut equation = numbers[0].to_string();
+
+            for (num_index, number) in numbers.iter().enumerate().skip(1) {
+                let do_addition = 1 << (num_index-1) & i != 0;
+
+                result = if do_addition { 
+                    equation = format!("{} + {}", equation, *number);
+                    result + *number
+                } else { 
+                    equation = format!("{} * {}", equation, *number);
+                    if *number == 1 {
+                        result
+                    } else {
+                        result * *number
                     }
-                }
-                pass = result == test_value;
+                };
 
-                if pass {
-                     println!("Equation passes: {test_value}={equation}");
-                     break;
+                 //Short circuit
+                 if result > test_value {
+                    break;
                 }
             }
+            pass = result == test_value;
 
             if pass {
-                total_calibration_result += test_value;
+                 trace!("Equation passes: {test_value}={equation}");
+                 break;
             }
         }
+
+        if pass {
+            total_calibration_result += test_value;
+        }
     }
 
     Ok(total_calibration_result)
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 7 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day7.txt");
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    let mut part1_total = 0;
+    let mut part2_input: Vec<(i64, Vec<i64>)> = Vec::new();
 
-    println!("Reading input from {}", input_file.display());
+    for (test_value, numbers) in parse_equations(input)? {
+        let mut pass = false;
+        let iterations = 2_i32.pow(numbers.len() as u32 - 1);
 
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
+        for i in 0..iterations {
 
-    let mut part1_total = 0;
-    let mut part2_input: Vec<(i64, Vec<i64>)> = Vec::new();
+            let mut result = numbers[0];
+            let mut equation = numbers[0].to_string();
 
-    for line in reader.lines() {
-        let line = line?;
+            for (num_index, number) in numbers.iter().enumerate().skip(1) {
+                let do_addition = 1 << (num_index-1) & i != 0;
 
-        if let Some((test_value, number_str)) = line.split_once(": ") {
-            let test_value: i64 = test_value.parse()?;
-            let numbers: Vec<i64> = number_str.split(" ").map(|s| s.parse().unwrap()).collect();
-
-            let mut pass = false;
-            let iterations = 2_i32.pow(numbers.len() as u32 - 1);
-
-            for i in 0..iterations {
-
-                let mut result = numbers[0];
-                let mut equation = numbers[0].to_string();
-
-                for (num_index, number) in numbers.iter().enumerate().skip(1) {
-                    let do_addition = 1 << (num_index-1) & i != 0;
-
-                    result = if do_addition { 
-                        equation = format!("{} + {}", equation, *number);
-                        result + *number
-                    } else { 
-                        equation = format!("{} * {}", equation, *number);
-                        if *number == 1 {
-                            result
-                        } else {
-                            result * *number
-                        }
-                    };
-
-                     //Short circuit
-                     if result > test_value {
-                        break;
+                result = if do_addition { 
+                    equation = format!("{} + {}", equation, *number);
+                    result + *number
+                } else { 
+                    equation = format!("{} * {}", equation, *number);
+                    if *number == 1 {
+                        result
+                    } else {
+                        result * *number
                     }
-                }
-                pass = result == test_value;
+                };
 
-                if pass {
-                    part1_total += test_value;
-                     break;
+                 //Short circuit
+                 if result > test_value {
+                    break;
                 }
             }
+            pass = result == test_value;
 
-            if !pass {
-                part2_input.push((test_value, numbers.clone()));
+            if pass {
+                part1_total += test_value;
+                 break;
             }
         }
+
+        if !pass {
+            part2_input.push((test_value, numbers));
+        }
     }
 
     let mut total_calibration_result = part1_total;
@@ -135,27 +118,27 @@ pub fn do_part2() -> anyhow::Result<i64> {
 
         match apply_expression(test_value, 0, Op::Concat, &numbers[..]) {
             Outcome::Pass(expression) => {
-                println!("Expression passed: 0{expression} = {test_value}");
+                trace!("Expression passed: 0{expression} = {test_value}");
                 total_calibration_result += test_value;
                 continue;
             },
-            Outcome::Fail(expression) => println!("Expression failed: 0{expression} = {test_value}"),
+            Outcome::Fail(expression) => trace!("Expression failed: 0{expression} = {test_value}"),
         }
         match apply_expression(test_value, 0, Op::Add, &numbers[..]) {
             Outcome::Pass(expression) => {
-                println!("Expression passed: 0{expression} = {test_value}");
+                trace!("Expression passed: 0{expression} = {test_value}");
                 total_calibration_result += test_value;
                 continue;
             },
-            Outcome::Fail(expression) => println!("Expression failed: 0{expression} = {test_value}"),
+            Outcome::Fail(expression) => trace!("Expression failed: 0{expression} = {test_value}"),
         }
         match apply_expression(test_value, 1, Op::Multiply, &numbers[..]) {
             Outcome::Pass(expression) => {
-                println!("Expression passed: 1{expression} = {test_value}");
+                trace!("Expression passed: 1{expression} = {test_value}");
                 total_calibration_result += test_value;
                 continue;
             },
-            Outcome::Fail(expression) => println!("Expression failed: 1{expression} = {test_value}"),
+            Outcome::Fail(expression) => trace!("Expression failed: 1{expression} = {test_value}"),
         }
     }
 
@@ -214,4 +197,21 @@ fn apply_expression(test_value: i64, input: i64, operator: Op, numbers: &[i64])
         Outcome::Pass(pass_expression) => Outcome::Pass(format!("{}{}", expression, pass_expression)),
         Outcome::Fail(fail_expression) => Outcome::Fail(format!("{}{}", expression, fail_expression)),
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day7-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(3749, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(11387, solve_part2(EXAMPLE).unwrap());
+    }
+}
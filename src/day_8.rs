@@ -1,22 +1,20 @@
 //https://adventofcode.com/2024/day/8
 
-use std::{collections::{HashMap, HashSet}, fs::File, io::{BufRead, BufReader}};
+use std::collections::{HashMap, HashSet};
+use anyhow::bail;
 
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 8 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day8.txt");
+//Locations of the antennas on each frequency
+pub type Antennas = HashMap<char, Vec<(i32, i32)>>;
 
-    println!("Reading input from {}", input_file.display());
+//The antennas, and the width and height of the map
+pub fn parse_antennas(input: &str) -> anyhow::Result<(Antennas, i32, i32)> {
+    let mut antenna_locations: Antennas = HashMap::new();
 
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
-    let mut antenna_locations: HashMap<char, Vec<(i32, i32)>> = HashMap::new();
-
-    let lines: Vec<String> = reader.lines().map(|l| l.unwrap()).collect();
-    let map_width = lines[0].len() as i32;
+    let lines: Vec<&str> = input.lines().collect();
+    let Some(first_line) = lines.first() else {
+        bail!("The map is empty");
+    };
+    let map_width = first_line.len() as i32;
     let map_height = lines.len() as i32;
 
     lines.iter().enumerate().for_each(|(row, line)| {
@@ -29,6 +27,12 @@ pub fn do_part1() -> anyhow::Result<i64> {
         })
     });
 
+    Ok((antenna_locations, map_width, map_height))
+}
+
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
+    let (antenna_locations, map_width, map_height) = parse_antennas(input)?;
+
     let mut antinodes: HashSet<(i32, i32)> = HashSet::new();
 
     //Calculate position of anti-nodes
@@ -63,32 +67,8 @@ pub fn do_part1() -> anyhow::Result<i64> {
     Ok(antinodes.len() as i64)
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 8 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day8.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let reader = BufReader::new(file);
-
-    let mut antenna_locations: HashMap<char, Vec<(i32, i32)>> = HashMap::new();
-
-    let lines: Vec<String> = reader.lines().map(|l| l.unwrap()).collect();
-    let map_width = lines[0].len() as i32;
-    let map_height = lines.len() as i32;
-
-    lines.iter().enumerate().for_each(|(row, line)| {
-        line.chars().enumerate().for_each(|(col, c)| {
-            if c != '.' {
-                antenna_locations.entry(c)
-                    .and_modify(|v| v.push((col as i32, row as i32)))
-                    .or_insert(Vec::from([(col as i32, row as i32)]));
-            }
-        })
-    });
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
+    let (antenna_locations, map_width, map_height) = parse_antennas(input)?;
 
     let mut antinodes: HashSet<(i32, i32)> = HashSet::new();
 
@@ -143,4 +123,21 @@ pub fn do_part2() -> anyhow::Result<i64> {
     }
 
     Ok(antinodes.len() as i64)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day8-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(14, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(34, solve_part2(EXAMPLE).unwrap());
+    }
+}
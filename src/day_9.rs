@@ -1,19 +1,21 @@
 //https://adventofcode.com/2024/day/9
 
-use std::{fs::File, io::{BufRead, BufReader}, ops::Range};
-
-pub fn do_part1() -> anyhow::Result<i64> {
-    println!("Day 9 - Part 1:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day9.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let mut reader = BufReader::new(file);
-    let mut input = String::new();
+use std::ops::Range;
+use anyhow::anyhow;
+
+//Sizes in blocks, alternating between files and the free space after each one
+pub fn parse_disk_map(input: &str) -> anyhow::Result<Vec<usize>> {
+    input.lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| c.to_digit(10)
+            .map(|size| size as usize)
+            .ok_or_else(|| anyhow!("Invalid block size '{c}' in the disk map")))
+        .collect()
+}
 
+pub fn solve_part1(input: &str) -> anyhow::Result<i64> {
     //File is 19,999 characters
     //First character is a file size in blocks, second character is size of space, this alternates repeatedly
     //We have 10,000 file block sizes and 9,999 space block sizes
@@ -23,9 +25,11 @@ pub fn do_part1() -> anyhow::Result<i64> {
 
     let mut checksum = 0_usize;
 
-    if let Ok(len) = reader.read_line(&mut input) {
-        let disk_map: Vec<usize> = input.chars().map(|c| c.to_digit(10).unwrap() as usize).collect();
-        
+    let disk_map = parse_disk_map(input)?;
+
+    if !disk_map.is_empty() {
+        let len = disk_map.len();
+
         let mut front_file_id = 0_usize;
         let mut rear_file_id = len / 2;
         let mut space_location = 0_usize;
@@ -35,6 +39,16 @@ pub fn do_part1() -> anyhow::Result<i64> {
         while front_file_id <= rear_file_id {
             let front_file_size = disk_map[front_file_id * 2];
             let rear_file_size = disk_map[rear_file_id * 2];
+
+            //Once the cursors meet, whatever hasn't been moved of the last file stays where it is
+            if front_file_id == rear_file_id {
+                if space_remaining == 0 {
+                    let blocks_left = if file_size_remaining > 0 { file_size_remaining } else { front_file_size };
+                    checksum += calc_checksum(front_file_id, space_location..space_location + blocks_left);
+                }
+                break;
+            }
+
             let space_available = disk_map[(front_file_id * 2) + 1];
  
             if space_remaining == 0 {
@@ -97,23 +111,15 @@ fn calc_checksum(file_id: usize, block_range: Range<usize>) -> usize {
     block_range.fold(0, |sum, idx| sum + (idx * file_id))
 }
 
-pub fn do_part2() -> anyhow::Result<i64> {
-    println!("Day 9 - Part 2:");
-    
-    let mut input_file = std::env::current_dir()?;
-    input_file.push("input\\day9.txt");
-
-    println!("Reading input from {}", input_file.display());
-
-    let file = File::open(input_file.clone())?;
-    let mut reader = BufReader::new(file);
-    let mut input = String::new();
+pub fn solve_part2(input: &str) -> anyhow::Result<i64> {
     let mut checksum = 0_usize;
 
-    if reader.read_line(&mut input).is_ok() {
-        let mut disk_map: Vec<(usize, usize)> = input.chars()
+    let block_sizes = parse_disk_map(input)?;
+
+    if !block_sizes.is_empty() {
+        let mut disk_map: Vec<(usize, usize)> = block_sizes.into_iter()
             .enumerate()
-            .map(|(idx, c)| (if idx % 2 == 0 { idx / 2 } else { 0 }, c.to_digit(10).unwrap() as usize))
+            .map(|(idx, size)| (if idx % 2 == 0 { idx / 2 } else { 0 }, size))
             .collect();
         
         //Reverse the disk map so we can insert at the end of the vector rather than at the start - makes indexing easier to manage
@@ -194,4 +200,21 @@ pub fn do_part2() -> anyhow::Result<i64> {
     }
 
     Ok(checksum as i64)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/day9-test.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(1928, solve_part1(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(2858, solve_part2(EXAMPLE).unwrap());
+    }
+}
@@ -0,0 +1,169 @@
+//Shapes made of grid cells, seen as polygons
+//
+//Outlines run along the corners of cells rather than through their middles, so the cell at (x,y)
+//covers the square between corner points (x,y) and (x+1,y+1). With y growing downwards, outer
+//boundaries are traced clockwise on screen and the boundaries of holes anticlockwise.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::misc_types::{Direction, Point};
+
+//A closed rectilinear polygon, the last vertex joins back to the first
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outline {
+    //Corner points where the outline changes direction, in the order they're walked
+    pub vertices: Vec<Point<i32>>,
+}
+
+impl Outline {
+    //Every vertex is a turn, so there's one side per corner
+    pub fn sides(&self) -> usize {
+        self.vertices.len()
+    }
+
+    //Shoelace formula, positive for an outer boundary and negative for a hole
+    pub fn signed_area(&self) -> i64 {
+        let next = self.vertices.iter().cycle().skip(1);
+        let twice_area: i64 = self.vertices.iter()
+            .zip(next)
+            .map(|(a, b)| a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64)
+            .sum();
+
+        twice_area / 2
+    }
+
+    pub fn area(&self) -> i64 {
+        self.signed_area().abs()
+    }
+
+    pub fn is_hole(&self) -> bool {
+        self.signed_area() < 0
+    }
+}
+
+//The side of cell facing outside, as the corner it starts at and the way it's walked
+fn side(cell: Point<i32>, outside: Direction) -> (Point<i32>, Direction) {
+    match outside {
+        Direction::North => (cell, Direction::East),
+        Direction::East => (cell + Point::new(1, 0), Direction::South),
+        Direction::South => (cell + Point::new(1, 1), Direction::West),
+        _ => (cell + Point::new(0, 1), Direction::North),
+    }
+}
+
+//Traces the boundaries of a set of cells into closed outlines, one for the outside of each separate
+//piece and one for each hole. Cells that only touch at a corner are joined there, so they share an
+//outline which passes through that corner twice, while holes that touch at a corner are kept apart.
+pub fn trace_outlines(cells: &HashSet<Point<i32>>) -> Vec<Outline> {
+    //Sides between a cell and a non-cell, keyed on the corner they start from
+    let mut unwalked: HashMap<Point<i32>, Vec<Direction>> = HashMap::new();
+    for &cell in cells {
+        for outside in Direction::CARDINAL {
+            if !cells.contains(&(cell + outside.to_point())) {
+                let (from, heading) = side(cell, outside);
+                unwalked.entry(from).or_default().push(heading);
+            }
+        }
+    }
+
+    let mut starts: Vec<Point<i32>> = unwalked.keys().copied().collect();
+    starts.sort_by_key(|p| (p.y, p.x));
+
+    let mut outlines = vec![];
+    for start in starts {
+        while let Some(first) = unwalked.get_mut(&start).and_then(|headings| headings.pop()) {
+            let mut vertices = vec![];
+            let (mut pos, mut heading) = (start + first.to_point(), first);
+
+            loop {
+                //Turning away from the cells first keeps to the outside of any corner where they meet
+                let closing = |d: Direction| pos == start && d == first;
+                let available = |d: Direction| unwalked.get(&pos).is_some_and(|headings| headings.contains(&d));
+                let next = [heading.turn_left(), heading, heading.turn_right()]
+                    .into_iter()
+                    .find(|&d| closing(d) || available(d))
+                    .expect("Every corner on an outline has a way out");
+
+                if next != heading {
+                    vertices.push(pos);
+                }
+                if closing(next) {
+                    break;
+                }
+
+                let headings = unwalked.get_mut(&pos).unwrap();
+                headings.retain(|&d| d != next);
+                (pos, heading) = (pos + next.to_point(), next);
+            }
+
+            //Keep vertices starting from the start corner if it's one
+            if let Some(index) = vertices.iter().position(|&v| v == start) {
+                vertices.rotate_left(index);
+            }
+            outlines.push(Outline { vertices });
+        }
+    }
+
+    outlines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(shape: &str) -> HashSet<Point<i32>> {
+        shape.lines()
+            .enumerate()
+            .flat_map(|(y, line)| line.chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(x, _)| Point::new(x as i32, y as i32)))
+            .collect()
+    }
+
+    #[test]
+    fn single_cell() {
+        let outlines = trace_outlines(&cells("#"));
+
+        assert_eq!(outlines, vec![Outline {
+            vertices: vec![Point::new(0, 0), Point::new(1, 0), Point::new(1, 1), Point::new(0, 1)],
+        }]);
+        assert_eq!(outlines[0].signed_area(), 1);
+    }
+
+    #[test]
+    fn concave_shapes() {
+        let outlines = trace_outlines(&cells("###\n#..\n###\n#..\n###"));
+
+        assert_eq!(outlines.len(), 1);
+        assert_eq!(outlines[0].sides(), 12);
+        assert_eq!(outlines[0].area(), 11);
+        assert!(!outlines[0].is_hole());
+    }
+
+    #[test]
+    fn holes_touching_at_a_corner_are_separate() {
+        let outlines = trace_outlines(&cells("\
+######
+###..#
+###..#
+#..###
+#..###
+######"));
+
+        assert_eq!(outlines.len(), 3);
+        assert_eq!(outlines.iter().filter(|o| o.is_hole()).count(), 2);
+        assert_eq!(outlines.iter().map(Outline::sides).sum::<usize>(), 12);
+        assert_eq!(outlines.iter().map(Outline::signed_area).sum::<i64>(), 28);
+    }
+
+    #[test]
+    fn pieces_touching_at_a_corner_share_an_outline() {
+        let outlines = trace_outlines(&cells("#.\n.#"));
+
+        assert_eq!(outlines.len(), 1);
+        assert_eq!(outlines[0].sides(), 8);
+        assert_eq!(outlines[0].signed_area(), 2);
+        assert_eq!(outlines[0].vertices.iter().filter(|&&v| v == Point::new(1, 1)).count(), 2);
+    }
+}
@@ -1,3 +1,10 @@
+use std::{fmt::Display, io::Read, path::{Path, PathBuf}, str::FromStr, sync::OnceLock};
+
+use anyhow::Context;
+
+use crate::info;
+
+//Prefer scan for new code, this returns untyped fields and skips over literals it can't find
 pub fn parse_formatted(input: String, format: String) -> Vec<String> {
     let mut result: Vec<String> = Vec::new();
 
@@ -36,4 +43,313 @@ pub fn parse_formatted(input: String, format: String) -> Vec<String> {
     }
 
     result
-}
\ No newline at end of file
+}
+
+//Typed scanning
+//
+//A format is literal text with a {} for each field, e.g. "p={},{} v={},{}".
+//Literals must match exactly and the whole line must be consumed, fields are parsed with FromStr
+//and returned as a tuple. Errors give the line and column (both 1-based) where matching failed.
+//
+//  let (x, y): (i32, i32) = scan("p=3,-4", "p={},{}")?;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScanError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+//Tuples of FromStr types that a format's fields can be parsed into
+pub trait ScanFields: Sized {
+    const FIELDS: usize;
+
+    //Each field is given with the column it starts at, errors are reported as (column, message)
+    fn parse_fields(fields: &[(usize, &str)]) -> Result<Self, (usize, String)>;
+}
+
+fn parse_field<T>((column, text): (usize, &str)) -> Result<T, (usize, String)>
+    where T: FromStr, T::Err: Display {
+    text.parse().map_err(|e| (column, format!("invalid value '{text}': {e}")))
+}
+
+macro_rules! impl_scan_fields {
+    ($count:literal; $($field:ident $index:tt),+) => {
+        impl<$($field),+> ScanFields for ($($field,)+)
+            where $($field: FromStr, $field::Err: Display),+ {
+            const FIELDS: usize = $count;
+
+            fn parse_fields(fields: &[(usize, &str)]) -> Result<Self, (usize, String)> {
+                Ok(($(parse_field::<$field>(fields[$index])?,)+))
+            }
+        }
+    };
+}
+
+impl_scan_fields!(1; A 0);
+impl_scan_fields!(2; A 0, B 1);
+impl_scan_fields!(3; A 0, B 1, C 2);
+impl_scan_fields!(4; A 0, B 1, C 2, D 3);
+impl_scan_fields!(5; A 0, B 1, C 2, D 3, E 4);
+impl_scan_fields!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+//Scan a single line, errors report it as line 1
+pub fn scan<T: ScanFields>(line: &str, format: &str) -> Result<T, ScanError> {
+    scan_line(1, line, format)
+}
+
+//Scan every line of the input with the same format
+pub fn scan_lines<T: ScanFields>(input: &str, format: &str) -> Result<Vec<T>, ScanError> {
+    input.lines()
+        .enumerate()
+        .map(|(index, line)| scan_line(index + 1, line, format))
+        .collect()
+}
+
+//Scan one line of a larger input, line_number is used when reporting errors
+pub fn scan_line<T: ScanFields>(line_number: usize, line: &str, format: &str) -> Result<T, ScanError> {
+    let error = |column: usize, message: String| ScanError { line: line_number, column, message };
+    //Columns count characters, the rest of the line always ends the original one
+    let column_of = |rest: &str| line[..line.len() - rest.len()].chars().count() + 1;
+
+    let literals: Vec<&str> = format.split("{}").collect();
+
+    if literals.len() - 1 != T::FIELDS {
+        return Err(error(1, format!("format '{format}' has {} fields but {} were expected", literals.len() - 1, T::FIELDS)))
+    }
+
+    //Without a literal between them there's no telling where one field ends and the next starts
+    if literals.len() > 2 && literals[1..literals.len() - 1].iter().any(|l| l.is_empty()) {
+        return Err(error(1, format!("format '{format}' has fields with nothing between them")))
+    }
+
+    let mut fields: Vec<(usize, &str)> = Vec::with_capacity(T::FIELDS);
+    let mut rest = line;
+
+    for (index, literal) in literals.iter().enumerate() {
+        //Every literal after the first is preceded by a field, which runs up to the literal
+        //or to the end of the line for a field at the end of the format
+        if index > 0 {
+            let column = column_of(rest);
+            let end = if literal.is_empty() {
+                rest.len()
+            } else {
+                rest.find(literal).ok_or_else(|| error(column, format!("expected a value followed by '{literal}', found '{rest}'")))?
+            };
+
+            if end == 0 {
+                return Err(error(column, format!("expected a value, found '{rest}'")))
+            }
+
+            fields.push((column, &rest[..end]));
+            rest = &rest[end..];
+        }
+
+        rest = rest.strip_prefix(literal)
+            .ok_or_else(|| error(column_of(rest), format!("expected '{literal}', found '{rest}'")))?;
+    }
+
+    if !rest.is_empty() {
+        return Err(error(column_of(rest), format!("unexpected '{rest}' at the end of the line")))
+    }
+
+    T::parse_fields(&fields).map_err(|(column, message)| error(column, message))
+}
+
+//Puzzle input location
+//
+//Inputs are found using the first of these that is set:
+// - an explicit file (or "-" for stdin) given with --input
+// - a directory given with --input-dir
+// - a directory named by the AOC_INPUT_DIR environment variable
+// - the "input" directory under the current directory, falling back to the one in the repo
+
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    //"-" means read from stdin, anything else is a file path
+    pub fn parse(value: &str) -> InputSource {
+        if value == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(value))
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputLocator {
+    pub input: Option<InputSource>,
+    pub input_dir: Option<PathBuf>,
+}
+
+impl InputLocator {
+    pub fn new() -> InputLocator {
+        InputLocator::default()
+    }
+
+    pub fn with_input(mut self, input: InputSource) -> InputLocator {
+        self.input = Some(input);
+        self
+    }
+
+    pub fn with_input_dir(mut self, input_dir: impl Into<PathBuf>) -> InputLocator {
+        self.input_dir = Some(input_dir.into());
+        self
+    }
+
+    pub fn locate(&self, day: u8) -> InputSource {
+        match &self.input {
+            Some(input) => input.clone(),
+            None => InputSource::File(self.input_dir().join(input_file_name(day))),
+        }
+    }
+
+    pub fn input_dir(&self) -> PathBuf {
+        if let Some(dir) = &self.input_dir {
+            return dir.clone()
+        }
+
+        if let Some(dir) = std::env::var_os(INPUT_DIR_ENV).filter(|d| !d.is_empty()) {
+            return PathBuf::from(dir)
+        }
+
+        //Prefer an input directory where we're running from, otherwise use the one alongside the sources
+        let local = Path::new("input");
+        if local.is_dir() {
+            local.to_path_buf()
+        } else {
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("input")
+        }
+    }
+}
+
+pub fn input_file_name(day: u8) -> String {
+    format!("day{day}.txt")
+}
+
+static LOCATOR: OnceLock<InputLocator> = OnceLock::new();
+static STDIN: OnceLock<String> = OnceLock::new();
+
+//Configure where inputs are read from, this can only be done once at startup
+pub fn set_locator(locator: InputLocator) -> anyhow::Result<()> {
+    LOCATOR.set(locator).map_err(|_| anyhow::anyhow!("Input locator has already been configured"))
+}
+
+pub fn locator() -> &'static InputLocator {
+    LOCATOR.get_or_init(InputLocator::new)
+}
+
+pub fn read_input(day: u8) -> anyhow::Result<String> {
+    let source = locator().locate(day);
+
+    info!("Reading input from {source}");
+
+    match source {
+        InputSource::File(path) => {
+            std::fs::read_to_string(&path).with_context(|| format!("Failed to read input {}", path.display()))
+        },
+        InputSource::Stdin => {
+            //Stdin can only be read once, keep hold of it in case more than one part asks for it
+            if let Some(input) = STDIN.get() {
+                return Ok(input.clone())
+            }
+
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            Ok(STDIN.get_or_init(|| input).clone())
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_input_wins() {
+        let locator = InputLocator::new()
+            .with_input_dir("somewhere")
+            .with_input(InputSource::parse("my_input.txt"));
+
+        assert_eq!(locator.locate(3), InputSource::File(PathBuf::from("my_input.txt")));
+        assert_eq!(InputSource::parse("-"), InputSource::Stdin);
+    }
+
+    #[test]
+    fn input_dir_is_joined_portably() {
+        let locator = InputLocator::new().with_input_dir("somewhere");
+
+        assert_eq!(locator.locate(12), InputSource::File(Path::new("somewhere").join("day12.txt")));
+    }
+
+    #[test]
+    fn scan_typed_fields() {
+        let robot: (i32, i32, i32, i32) = scan("p=0,4 v=3,-3", "p={},{} v={},{}").unwrap();
+        assert_eq!(robot, (0, 4, 3, -3));
+
+        let (label, x, y): (char, i64, i64) = scan("Button B: X+22, Y+67", "Button {}: X+{}, Y+{}").unwrap();
+        assert_eq!((label, x, y), ('B', 22, 67));
+
+        let (name,): (String,) = scan("Register A", "Register {}").unwrap();
+        assert_eq!(name, "A");
+    }
+
+    #[test]
+    fn scan_anchors_literals() {
+        //A missing leading literal
+        let error = scan::<(i32, i32)>("q=1,2", "p={},{}").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+
+        //A missing separator
+        let error = scan::<(i32, i32)>("p=1;2", "p={},{}").unwrap_err();
+        assert_eq!(error.column, 3);
+
+        //Trailing text
+        let error = scan::<(i32, i32)>("p=1,2 v=3", "p={},{} ").unwrap_err();
+        assert_eq!(error.column, 7);
+        let error = scan::<(i32,)>("X+22, Y", "X+{},").unwrap_err();
+        assert_eq!(error.column, 6);
+    }
+
+    #[test]
+    fn scan_reports_line_and_column() {
+        let error = scan_lines::<(i32, i32)>("p=1,2\np=3,x4\n", "p={},{}").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.to_string(), "line 2, column 5: invalid value 'x4': invalid digit found in string");
+
+        let error = scan_lines::<(i32, i32)>("p=1,2\np=,4", "p={},{}").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        assert_eq!(scan_lines::<(i32, i32)>("p=1,2\np=3,4\n", "p={},{}").unwrap(), vec![(1, 2), (3, 4)]);
+    }
+
+    #[test]
+    fn scan_rejects_bad_formats() {
+        assert!(scan::<(i32, i32)>("12", "{}{}").is_err());
+        assert!(scan::<(i32, i32)>("1,2,3", "{},{},{}").is_err());
+    }
+}
@@ -0,0 +1,294 @@
+//Just enough JSON for the runner's own files and output, not a general purpose library.
+//Integers are kept as i128 so large puzzle answers survive a round trip.
+
+use std::fmt::{Display, Write};
+
+use anyhow::{anyhow, bail};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<K: Into<String>>(fields: impl IntoIterator<Item = (K, Json)>) -> Json {
+        Json::Object(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            Json::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn parse(text: &str) -> anyhow::Result<Json> {
+        let mut parser = Parser { chars: text.char_indices().peekable(), text };
+        let value = parser.value()?;
+
+        parser.skip_whitespace();
+        if let Some((index, c)) = parser.chars.next() {
+            bail!("Unexpected '{c}' at offset {index} after the end of the JSON value");
+        }
+
+        Ok(value)
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{value}"),
+            Json::Int(value) => write!(f, "{value}"),
+            Json::Float(value) if value.is_finite() => write!(f, "{value:?}"),
+            Json::Float(_) => write!(f, "null"),
+            Json::Str(value) => write_string(f, value),
+            Json::Array(items) => {
+                f.write_char('[')?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_char(']')
+            },
+            Json::Object(fields) => {
+                f.write_char('{')?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            },
+        }
+    }
+}
+
+fn write_string(f: &mut std::fmt::Formatter<'_>, value: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    text: &'a str,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> anyhow::Result<()> {
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((index, c)) => bail!("Expected '{expected}' at offset {index}, found '{c}'"),
+            None => bail!("Expected '{expected}', found the end of the input"),
+        }
+    }
+
+    fn value(&mut self) -> anyhow::Result<Json> {
+        self.skip_whitespace();
+
+        let &(index, c) = self.chars.peek().ok_or_else(|| anyhow!("Unexpected end of the input"))?;
+
+        match c {
+            '{' => self.object(),
+            '[' => self.array(),
+            '"' => Ok(Json::Str(self.string()?)),
+            't' => self.keyword("true", Json::Bool(true)),
+            'f' => self.keyword("false", Json::Bool(false)),
+            'n' => self.keyword("null", Json::Null),
+            '-' | '0'..='9' => self.number(),
+            _ => bail!("Unexpected '{c}' at offset {index}"),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Json) -> anyhow::Result<Json> {
+        for expected in keyword.chars() {
+            self.expect(expected)?;
+        }
+        Ok(value)
+    }
+
+    fn number(&mut self) -> anyhow::Result<Json> {
+        let start = self.chars.peek().map_or(self.text.len(), |&(index, _)| index);
+        let mut end = start;
+
+        while let Some((index, c)) = self.chars.next_if(|(_, c)| matches!(c, '-' | '+' | '.' | 'e' | 'E' | '0'..='9')) {
+            end = index + c.len_utf8();
+        }
+
+        let number = &self.text[start..end];
+
+        if let Ok(value) = number.parse::<i128>() {
+            return Ok(Json::Int(value))
+        }
+
+        number.parse::<f64>()
+            .map(Json::Float)
+            .map_err(|_| anyhow!("Invalid number '{number}' at offset {start}"))
+    }
+
+    fn string(&mut self) -> anyhow::Result<String> {
+        self.expect('"')?;
+        let mut value = String::new();
+
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(value),
+                Some((index, '\\')) => match self.chars.next() {
+                    Some((_, '"')) => value.push('"'),
+                    Some((_, '\\')) => value.push('\\'),
+                    Some((_, '/')) => value.push('/'),
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 'r')) => value.push('\r'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, 'b')) => value.push('\u{8}'),
+                    Some((_, 'f')) => value.push('\u{c}'),
+                    Some((_, 'u')) => {
+                        let hex: String = (0..4).filter_map(|_| self.chars.next().map(|(_, c)| c)).collect();
+                        let code = u32::from_str_radix(&hex, 16).ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| anyhow!("Invalid unicode escape at offset {index}"))?;
+                        value.push(code);
+                    },
+                    _ => bail!("Invalid escape at offset {index}"),
+                },
+                Some((_, c)) => value.push(c),
+                None => bail!("Unterminated string"),
+            }
+        }
+    }
+
+    fn array(&mut self) -> anyhow::Result<Json> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == ']').is_some() {
+            return Ok(Json::Array(items))
+        }
+
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+
+            match self.chars.next() {
+                Some((_, ',')) => continue,
+                Some((_, ']')) => return Ok(Json::Array(items)),
+                Some((index, c)) => bail!("Expected ',' or ']' at offset {index}, found '{c}'"),
+                None => bail!("Unterminated array"),
+            }
+        }
+    }
+
+    fn object(&mut self) -> anyhow::Result<Json> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == '}').is_some() {
+            return Ok(Json::Object(fields))
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+
+            match self.chars.next() {
+                Some((_, ',')) => continue,
+                Some((_, '}')) => return Ok(Json::Object(fields)),
+                Some((index, c)) => bail!("Expected ',' or '}}' at offset {index}, found '{c}'"),
+                None => bail!("Unterminated object"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let value = Json::object([
+            ("day", Json::Int(17)),
+            ("answer", Json::Str("4,6,\"1\"\n".into())),
+            ("big", Json::Int(i64::MAX as i128 * 4)),
+            ("elapsed", Json::Float(0.25)),
+            ("error", Json::Null),
+            ("parts", Json::Array(vec![Json::Bool(true), Json::Array(vec![])])),
+        ]);
+
+        assert_eq!(Json::parse(&value.to_string()).unwrap(), value);
+    }
+
+    #[test]
+    fn parse_with_whitespace() {
+        let value = Json::parse(" { \"results\" : [ { \"day\" : 1 , \"median_ns\" : 1500 } ] } ").unwrap();
+        let results = value.get("results").and_then(Json::as_array).unwrap();
+
+        assert_eq!(results[0].get("day").and_then(Json::as_int), Some(1));
+        assert_eq!(results[0].get("median_ns").and_then(Json::as_int), Some(1500));
+        assert_eq!(Json::parse("-1.5e3").unwrap(), Json::Float(-1500.0));
+        assert_eq!(Json::parse("\"\\u0041\"").unwrap(), Json::Str("A".into()));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Json::parse("").is_err());
+        assert!(Json::parse("{\"a\" 1}").is_err());
+        assert!(Json::parse("[1, 2").is_err());
+        assert!(Json::parse("tru").is_err());
+        assert!(Json::parse("1 2").is_err());
+        assert!(Json::parse("\"open").is_err());
+    }
+}
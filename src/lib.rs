@@ -0,0 +1,49 @@
+//! Advent of Code 2024 solutions, plus the helpers and tooling around them.
+//!
+//! - [`solver`] registers every day's solutions behind the [`solver::Solver`] trait
+//! - `day_N` modules hold each day's `solve_part1`/`solve_part2`, taking the puzzle input as text
+//! - [`input_utils`], [`misc_types`], [`algorithms`], [`geometry`], [`pathfinding`], [`corridor_graph`] and [`json`] are the shared helpers the days are built from
+//! - [`cli`], [`commands`], [`answers`], [`bench`](mod@bench), [`report`] and [`log`] make up the runner
+
+pub mod algorithms;
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod commands;
+pub mod corridor_graph;
+pub mod geometry;
+pub mod input_utils;
+pub mod json;
+pub mod log;
+pub mod misc_types;
+pub mod pathfinding;
+pub mod report;
+pub mod solver;
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20; //TODO
+pub mod day_21; //TODO
+pub mod day_22; //TODO
+pub mod day_23; //TODO
+pub mod day_24; //TODO
+pub mod day_25; //TODO
+
+#[cfg(test)]
+mod regression_tests;
@@ -0,0 +1,120 @@
+//Crate-wide logging with levels, set from the -v/-q flags
+//
+//Messages go to stderr so the answers on stdout can be piped cleanly.
+//Use the error!, warn!, info!, debug! and trace! macros rather than println! for anything that isn't an answer.
+
+use std::{fmt::Display, sync::atomic::{AtomicU8, Ordering}};
+
+use colored::Colorize;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    //Quiet only shows errors, each -v shows one more level than the default
+    pub fn from_verbosity(verbose: u8, quiet: bool) -> Level {
+        match (quiet, verbose) {
+            (true, _) => Level::Error,
+            (false, 0) => Level::Warn,
+            (false, 1) => Level::Info,
+            (false, 2) => Level::Debug,
+            (false, _) => Level::Trace,
+        }
+    }
+
+    fn from_u8(value: u8) -> Level {
+        match value {
+            0 => Level::Error,
+            1 => Level::Warn,
+            2 => Level::Info,
+            3 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Error => write!(f, "{}", "ERROR".red().bold()),
+            Level::Warn => write!(f, "{}", "WARN ".yellow()),
+            Level::Info => write!(f, "{}", "INFO ".green()),
+            Level::Debug => write!(f, "{}", "DEBUG".blue()),
+            Level::Trace => write!(f, "{}", "TRACE".dimmed()),
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::from_u8(LEVEL.load(Ordering::Relaxed))
+}
+
+//Check before doing expensive work that only feeds a log message, like rendering a map
+pub fn enabled(level: Level) -> bool {
+    level <= self::level()
+}
+
+pub fn log(level: Level, message: std::fmt::Arguments) {
+    if enabled(level) {
+        eprintln!("{level} {message}");
+    }
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)*) => { $crate::log::log($crate::log::Level::Error, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => { $crate::log::log($crate::log::Level::Warn, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::log::log($crate::log::Level::Info, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::log::log($crate::log::Level::Debug, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => { $crate::log::log($crate::log::Level::Trace, format_args!($($arg)*)) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbosity_flags_pick_the_level() {
+        assert_eq!(Level::from_verbosity(0, false), Level::Warn);
+        assert_eq!(Level::from_verbosity(1, false), Level::Info);
+        assert_eq!(Level::from_verbosity(2, false), Level::Debug);
+        assert_eq!(Level::from_verbosity(5, false), Level::Trace);
+        assert_eq!(Level::from_verbosity(2, true), Level::Error);
+    }
+
+    #[test]
+    fn levels_round_trip() {
+        for level in [Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace] {
+            assert_eq!(Level::from_u8(level as u8), level);
+        }
+    }
+}
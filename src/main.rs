@@ -1,42 +1,37 @@
-pub mod input_utils;
-pub mod misc_types;
-pub mod day_1;
-pub mod day_2;
-pub mod day_3;
-pub mod day_4;
-pub mod day_5;
-pub mod day_6;
-pub mod day_7;
-pub mod day_8;
-pub mod day_9;
-pub mod day_10;
-pub mod day_11;
-pub mod day_12;
-pub mod day_13;
-pub mod day_14;
-pub mod day_15;
-pub mod day_16;
-pub mod day_17;
-pub mod day_18;
-pub mod day_19;
-pub mod day_20; //TODO
-pub mod day_21; //TODO
-pub mod day_22; //TODO
-pub mod day_23; //TODO
-pub mod day_24; //TODO
-pub mod day_25; //TODO
+//Command-line front end, the solvers and tooling all live in the adventofcode_24 library
 
+use adventofcode_24::cli::{self, Command};
+use adventofcode_24::commands;
 use colored::Colorize;
 
 fn main() -> anyhow::Result<()> {
-    
-    if let Ok(answer) = day_20::do_part1() {
-        println!("{} {}", "Part 1 answer:".green(), answer.to_string().blue().bold());
-    }
+    let command = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{} {e}", "Error:".red().bold());
+            eprintln!();
+            eprintln!("{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
 
-    if let Ok(answer) = day_20::do_part2() {
-        println!("{} {}", "Part 2 answer:".green(), answer.to_string().blue().bold());
+    match command {
+        Command::Run(options) => commands::run(&options),
+        Command::Verify(options) => {
+            if !commands::verify(&options)? {
+                std::process::exit(1);
+            }
+            Ok(())
+        },
+        Command::Bench(options) => {
+            if !commands::bench(&options)? {
+                std::process::exit(1);
+            }
+            Ok(())
+        },
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
     }
-
-    Ok(())
 }
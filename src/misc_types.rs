@@ -1,52 +1,149 @@
-use std::{fmt::Display, ops::{Add, AddAssign, Sub, SubAssign}};
+use std::{fmt::Display, ops::{Add, AddAssign, Div, Index, IndexMut, Mul, Neg, Rem, Sub, SubAssign}};
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Vector2D {
-    pub x: i32,
-    pub y: i32,
+use anyhow::{anyhow, bail};
+
+//Integer types that can be used as Rect coordinates
+pub trait Coordinate:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
 }
 
-impl Vector2D {
-    pub fn new(x: i32, y: i32) -> Vector2D {
-        Vector2D { x, y }
-    }
+macro_rules! impl_coordinate {
+    ($($t:ty),+) => {
+        $(impl Coordinate for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })+
+    };
 }
 
-impl AddAssign for Vector2D {
-    fn add_assign(&mut self, other: Self) {
-        self.x += other.x;
-        self.y += other.y;
+impl_coordinate!(i32, i64, isize, usize);
+
+//Bring value into the range start..start + length, counting round from the other end when it's outside
+fn wrap_coordinate<T: Coordinate>(value: T, start: T, length: T) -> T {
+    if value >= start {
+        start + (value - start) % length
+    } else {
+        //Works out the same as rem_euclid but without going negative, so unsigned types can be used too
+        let behind = (start - value) % length;
+        if behind == T::ZERO { start } else { start + length - behind }
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Rect {
-    pub left: i32,
-    pub top: i32,
-    pub right: i32,
-    pub bottom: i32,
+//A rectangle covering left..right and top..bottom, the right and bottom edges are not included
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rect<T = i32> {
+    pub left: T,
+    pub top: T,
+    pub right: T,
+    pub bottom: T,
 }
 
-impl Rect {
-    pub fn new(left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+impl<T: Coordinate> Rect<T> {
+    pub fn new(left: T, top: T, right: T, bottom: T) -> Rect<T> {
         Rect { left, top, right, bottom }
     }
 
-    pub fn is_inside(&self, pos: Point<i32>) -> bool {
+    //A rectangle at the origin, such as the bounds of a map
+    pub fn from_size(width: T, height: T) -> Rect<T> {
+        Rect::new(T::ZERO, T::ZERO, width, height)
+    }
+
+    pub fn width(&self) -> T {
+        if self.right > self.left { self.right - self.left } else { T::ZERO }
+    }
+
+    pub fn height(&self) -> T {
+        if self.bottom > self.top { self.bottom - self.top } else { T::ZERO }
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width() == T::ZERO || self.height() == T::ZERO
+    }
+
+    pub fn is_inside(&self, pos: Point<T>) -> bool {
         pos.x >= self.left
         && pos.x < self.right
         && pos.y >= self.top
         && pos.y < self.bottom
     }
+
+    //Every point inside, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point<T>> {
+        let Rect { left, top, right, bottom } = *self;
+
+        std::iter::successors(Some(top), |&y| Some(y + T::ONE))
+            .take_while(move |&y| y < bottom)
+            .flat_map(move |y| std::iter::successors(Some(left), |&x| Some(x + T::ONE))
+                .take_while(move |&x| x < right)
+                .map(move |x| Point::new(x, y)))
+    }
+
+    //The area covered by both, None if they don't overlap
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let overlap = Rect::new(
+            self.left.max(other.left),
+            self.top.max(other.top),
+            self.right.min(other.right),
+            self.bottom.min(other.bottom),
+        );
+
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    //The smallest rectangle containing both
+    pub fn union(&self, other: &Rect<T>) -> Rect<T> {
+        Rect::new(
+            self.left.min(other.left),
+            self.top.min(other.top),
+            self.right.max(other.right),
+            self.bottom.max(other.bottom),
+        )
+    }
+
+    //Top left, top right, bottom left then bottom right
+    //When a side has odd length the middle row or column isn't part of any quadrant
+    pub fn quadrants(&self) -> [Rect<T>; 4] {
+        let two = T::ONE + T::ONE;
+        let (half_width, half_height) = (self.width() / two, self.height() / two);
+        let (mid_x, mid_y) = (self.left + half_width, self.top + half_height);
+        let (right_x, bottom_y) = (self.right - half_width, self.bottom - half_height);
+
+        [
+            Rect::new(self.left, self.top, mid_x, mid_y),
+            Rect::new(right_x, self.top, self.right, mid_y),
+            Rect::new(self.left, bottom_y, mid_x, self.bottom),
+            Rect::new(right_x, bottom_y, self.right, self.bottom),
+        ]
+    }
+
+    //Moves the point back inside as if the edges were joined, for toroidal worlds.
+    //An empty rect has nowhere to wrap to, so the point is left where it is
+    pub fn wrap(&self, pos: Point<T>) -> Point<T> {
+        if self.is_empty() {
+            return pos
+        }
+
+        Point::new(
+            wrap_coordinate(pos.x, self.left, self.width()),
+            wrap_coordinate(pos.y, self.top, self.height()),
+        )
+    }
 }
 
-impl Display for Rect {
+impl<T: Display> Display for Rect<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Rect(L:{},T:{},R:{},B:{})", self.left, self.top, self.right, self.bottom)
     }
 }
 
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+//A position or a displacement on a 2D map, see PointN for more dimensions
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Point<T> {
     pub x: T,
     pub y: T,
@@ -73,30 +170,101 @@ impl<T> Point<T>
     }
 }
 
-impl Point<i32> {
-    pub fn inside(self, bounding_box: Rect) -> bool {
+impl<T> Point<T>
+    where T: Ord + Copy + Add<Output = T> + Sub<Output = T> {
+    //Steps between the points moving only along the axes
+    pub fn manhattan(self, other: Point<T>) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    //Steps between the points when diagonal moves are allowed
+    pub fn chebyshev(self, other: Point<T>) -> T {
+        distance(self.x, other.x).max(distance(self.y, other.y))
+    }
+}
+
+impl<T> Point<T>
+    where T: Copy + Add<Output = T> + Mul<Output = T> {
+    pub fn dot(self, other: Point<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+//Absolute difference that works for unsigned types too
+fn distance<T>(a: T, b: T) -> T
+    where T: Ord + Sub<Output = T> {
+    if a > b { a - b } else { b - a }
+}
+
+impl<T: Coordinate> Point<T> {
+    pub fn inside(self, bounding_box: Rect<T>) -> bool {
         bounding_box.is_inside(self)
     }
 }
 
-impl From<Point<i32>> for Point<usize> {
-    fn from(value: Point<i32>) -> Self {
-        Point {
-            x: value.x.try_into().unwrap(),
-            y: value.y.try_into().unwrap(),
+impl Point<i32> {
+    //Neighbours in the order of Direction::CARDINAL, which may lie off the map
+    pub fn neighbours4(self) -> [Point<i32>; 4] {
+        Direction::CARDINAL.map(|direction| self + direction.to_point())
+    }
+
+    //Neighbours in the order of Direction::ALL, which may lie off the map
+    pub fn neighbours8(self) -> [Point<i32>; 8] {
+        Direction::ALL.map(|direction| self + direction.to_point())
+    }
+}
+
+//A point that can't be represented with the target coordinate type, such as one with negative coordinates as usize
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PointOutOfRange {
+    pub x: i128,
+    pub y: i128,
+}
+
+impl Display for PointOutOfRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Point (x:{},y:{}) is out of range", self.x, self.y)
+    }
+}
+
+impl std::error::Error for PointOutOfRange {}
+
+impl TryFrom<Point<i32>> for Point<usize> {
+    type Error = PointOutOfRange;
+
+    fn try_from(value: Point<i32>) -> Result<Self, Self::Error> {
+        match (value.x.try_into(), value.y.try_into()) {
+            (Ok(x), Ok(y)) => Ok(Point { x, y }),
+            _ => Err(PointOutOfRange { x: value.x as i128, y: value.y as i128 }),
         }
     }
 }
 
-impl From<Point<usize>> for Point<i32> {
-    fn from(value: Point<usize>) -> Self {
-        Point {
-            x: value.x.try_into().unwrap(),
-            y: value.y.try_into().unwrap(),
+impl TryFrom<Point<usize>> for Point<i32> {
+    type Error = PointOutOfRange;
+
+    fn try_from(value: Point<usize>) -> Result<Self, Self::Error> {
+        match (value.x.try_into(), value.y.try_into()) {
+            (Ok(x), Ok(y)) => Ok(Point { x, y }),
+            _ => Err(PointOutOfRange { x: value.x as i128, y: value.y as i128 }),
         }
     }
 }
 
+impl Point<usize> {
+    //One step in the given direction, None if that would go past the top or left edge
+    pub fn offset(self, direction: Direction) -> Option<Point<usize>> {
+        self.checked_add(direction.to_point())
+    }
+
+    pub fn checked_add(self, step: Point<i32>) -> Option<Point<usize>> {
+        Some(Point {
+            x: self.x.checked_add_signed(step.x as isize)?,
+            y: self.y.checked_add_signed(step.y as isize)?,
+        })
+    }
+}
+
 impl<T: Add<Output = T>> Add for Point<T> {
     type Output = Self;
 
@@ -119,6 +287,50 @@ impl<T: Sub<Output = T>> Sub for Point<T> {
     }
 }
 
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, scale: T) -> Self::Output {
+        Self {
+            x: self.x * scale,
+            y: self.y * scale,
+        }
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Point<T> {
+    type Output = Self;
+
+    fn div(self, divisor: T) -> Self::Output {
+        Self {
+            x: self.x / divisor,
+            y: self.y / divisor,
+        }
+    }
+}
+
+impl<T: Rem<Output = T> + Copy> Rem<T> for Point<T> {
+    type Output = Self;
+
+    fn rem(self, divisor: T) -> Self::Output {
+        Self {
+            x: self.x % divisor,
+            y: self.y % divisor,
+        }
+    }
+}
+
 impl<T> AddAssign for Point<T>
     where T: AddAssign, {
     fn add_assign(&mut self, other: Self) {
@@ -140,4 +352,630 @@ where T: Display {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "(x:{},y:{})", self.x, self.y)
     }
-}
\ No newline at end of file
+}
+
+//A point with any number of dimensions, with the same operations as Point
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PointN<T, const N: usize> {
+    pub coords: [T; N],
+}
+
+pub type Point3<T> = PointN<T, 3>;
+
+impl<T, const N: usize> PointN<T, N> {
+    pub fn new(coords: [T; N]) -> PointN<T, N> {
+        PointN { coords }
+    }
+
+    //Apply f to each pair of coordinates
+    fn zip_with<U, V>(self, other: PointN<U, N>, mut f: impl FnMut(T, U) -> V) -> PointN<V, N> {
+        let mut others = other.coords.into_iter();
+        PointN { coords: self.coords.map(|c| f(c, others.next().unwrap())) }
+    }
+}
+
+impl<T> Point3<T> {
+    pub fn x(&self) -> &T {
+        &self.coords[0]
+    }
+
+    pub fn y(&self) -> &T {
+        &self.coords[1]
+    }
+
+    pub fn z(&self) -> &T {
+        &self.coords[2]
+    }
+}
+
+impl<T, const N: usize> PointN<T, N>
+    where T: Ord + Copy + Add<Output = T> + Sub<Output = T> {
+    pub fn manhattan(self, other: PointN<T, N>) -> T {
+        self.zip_with(other, distance).coords.into_iter().reduce(|a, b| a + b).expect("Points need at least one dimension")
+    }
+
+    pub fn chebyshev(self, other: PointN<T, N>) -> T {
+        self.zip_with(other, distance).coords.into_iter().max().expect("Points need at least one dimension")
+    }
+}
+
+impl<T, const N: usize> PointN<T, N>
+    where T: Copy + Add<Output = T> + Mul<Output = T> {
+    pub fn dot(self, other: PointN<T, N>) -> T {
+        self.zip_with(other, |a, b| a * b).coords.into_iter().reduce(|a, b| a + b).expect("Points need at least one dimension")
+    }
+}
+
+impl<T: Default, const N: usize> Default for PointN<T, N> {
+    fn default() -> Self {
+        PointN { coords: std::array::from_fn(|_| T::default()) }
+    }
+}
+
+impl<T> From<Point<T>> for PointN<T, 2> {
+    fn from(point: Point<T>) -> Self {
+        PointN { coords: [point.x, point.y] }
+    }
+}
+
+impl<T> From<PointN<T, 2>> for Point<T> {
+    fn from(point: PointN<T, 2>) -> Self {
+        let [x, y] = point.coords;
+        Point { x, y }
+    }
+}
+
+impl<T, const N: usize> Index<usize> for PointN<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.coords[axis]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for PointN<T, N> {
+    fn index_mut(&mut self, axis: usize) -> &mut T {
+        &mut self.coords[axis]
+    }
+}
+
+impl<T: Add<Output = T>, const N: usize> Add for PointN<T, N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.zip_with(other, |a, b| a + b)
+    }
+}
+
+impl<T: Sub<Output = T>, const N: usize> Sub for PointN<T, N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.zip_with(other, |a, b| a - b)
+    }
+}
+
+impl<T: Neg<Output = T>, const N: usize> Neg for PointN<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        PointN { coords: self.coords.map(|c| -c) }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy, const N: usize> Mul<T> for PointN<T, N> {
+    type Output = Self;
+
+    fn mul(self, scale: T) -> Self::Output {
+        PointN { coords: self.coords.map(|c| c * scale) }
+    }
+}
+
+impl<T: Div<Output = T> + Copy, const N: usize> Div<T> for PointN<T, N> {
+    type Output = Self;
+
+    fn div(self, divisor: T) -> Self::Output {
+        PointN { coords: self.coords.map(|c| c / divisor) }
+    }
+}
+
+impl<T: Rem<Output = T> + Copy, const N: usize> Rem<T> for PointN<T, N> {
+    type Output = Self;
+
+    fn rem(self, divisor: T) -> Self::Output {
+        PointN { coords: self.coords.map(|c| c % divisor) }
+    }
+}
+
+impl<T: AddAssign, const N: usize> AddAssign for PointN<T, N> {
+    fn add_assign(&mut self, other: Self) {
+        for (c, o) in self.coords.iter_mut().zip(other.coords) {
+            *c += o;
+        }
+    }
+}
+
+impl<T: SubAssign, const N: usize> SubAssign for PointN<T, N> {
+    fn sub_assign(&mut self, other: Self) {
+        for (c, o) in self.coords.iter_mut().zip(other.coords) {
+            *c -= o;
+        }
+    }
+}
+
+impl<T: Display, const N: usize> Display for PointN<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        for (axis, c) in self.coords.iter().enumerate() {
+            if axis > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{c}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+//Compass directions on a map where y grows downwards, so North is Point(0, -1)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    //The 4 way neighbourhood, clockwise from North
+    pub const CARDINAL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    //The 8 way neighbourhood, clockwise from North
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    fn rotate(self, eighths: usize) -> Direction {
+        Direction::ALL[(self as usize + eighths) % 8]
+    }
+
+    //Quarter turn anticlockwise
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    //Quarter turn clockwise
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !Direction::CARDINAL.contains(&self)
+    }
+
+    //The unit step in this direction
+    pub fn to_point(self) -> Point<i32> {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::NorthEast => Point::new(1, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::SouthEast => Point::new(1, 1),
+            Direction::South => Point::new(0, 1),
+            Direction::SouthWest => Point::new(-1, 1),
+            Direction::West => Point::new(-1, 0),
+            Direction::NorthWest => Point::new(-1, -1),
+        }
+    }
+
+    //The direction of a unit step, None for anything else
+    pub fn from_point(step: Point<i32>) -> Option<Direction> {
+        Direction::ALL.into_iter().find(|direction| direction.to_point() == step)
+    }
+
+    //Which way to head from one point to reach another in a straight or diagonal line
+    pub fn towards<T>(from: Point<T>, to: Point<T>) -> Option<Direction>
+        where T: Ord + Copy + Sub<Output = T> {
+        let straight = from.x == to.x || from.y == to.y;
+
+        if !straight && distance(from.x, to.x) != distance(from.y, to.y) {
+            return None
+        }
+
+        let step = |a: T, b: T| b.cmp(&a) as i32;
+        Direction::from_point(Point::new(step(from.x, to.x), step(from.y, to.y)))
+    }
+}
+
+impl From<Direction> for Point<i32> {
+    fn from(direction: Direction) -> Self {
+        direction.to_point()
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+//A rectangular map stored row by row, cells are addressed by Point with x as the column and y the row
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+        where T: Clone {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    //Build a grid from puzzle text, map returns None for characters that aren't allowed
+    pub fn parse(input: &str, mut map: impl FnMut(char) -> Option<T>) -> anyhow::Result<Grid<T>> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            let before = cells.len();
+
+            for (col, c) in line.chars().enumerate() {
+                let cell = map(c).ok_or_else(|| anyhow!("Unexpected '{c}' at line {}, column {}", row + 1, col + 1))?;
+                cells.push(cell);
+            }
+
+            let line_width = cells.len() - before;
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => bail!("Line {} is {line_width} wide, expected {width}", row + 1),
+                _ => (),
+            }
+            height += 1;
+        }
+
+        Ok(Grid { width: width.unwrap_or(0), height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Point<i32>) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    fn index_of(&self, pos: Point<i32>) -> Option<usize> {
+        let x = usize::try_from(pos.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(pos.y).ok().filter(|&y| y < self.height)?;
+
+        Some(y * self.width + x)
+    }
+
+    fn point_of(&self, index: usize) -> Point<i32> {
+        Point::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    //None when the position is off the grid
+    pub fn get(&self, pos: Point<i32>) -> Option<&T> {
+        self.index_of(pos).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, pos: Point<i32>) -> Option<&mut T> {
+        self.index_of(pos).map(|index| &mut self.cells[index])
+    }
+
+    //Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point<i32>, &T)> {
+        self.cells.iter().enumerate().map(|(index, cell)| (self.point_of(index), cell))
+    }
+
+    //Positions of all the cells matching the predicate, row by row
+    pub fn positions<'a>(&'a self, mut predicate: impl FnMut(&T) -> bool + 'a) -> impl Iterator<Item = Point<i32>> + 'a {
+        self.iter().filter(move |(_, cell)| predicate(cell)).map(|(pos, _)| pos)
+    }
+
+    //Position of the first cell matching the predicate, handy for start and end markers
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Point<i32>> {
+        self.positions(predicate).next()
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+//Indexing panics off the grid, use get for positions that might be outside
+impl<T> Index<Point<i32>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point<i32>) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{pos} is outside the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point<i32>> for Grid<T> {
+    fn index_mut(&mut self, pos: Point<i32>) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| panic!("{pos} is outside the {width}x{height} grid"))
+    }
+}
+
+//Renders one line per row, which gives back the puzzle text for a Grid<char>
+impl<T> Display for Grid<T>
+where T: Display {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#####\n#S.E#\n#####\n";
+
+    #[test]
+    fn direction_turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::NorthEast.turn_right(), Direction::SouthEast);
+        assert_eq!(Direction::SouthWest.opposite(), Direction::NorthEast);
+
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.opposite().to_point(), -direction.to_point());
+            assert_eq!(Direction::from_point(direction.into()), Some(direction));
+        }
+        assert!(Direction::CARDINAL.iter().all(|d| !d.is_diagonal()));
+    }
+
+    #[test]
+    fn direction_towards() {
+        let origin = Point::new(2, 2);
+
+        assert_eq!(Direction::towards(origin, Point::new(2, -5)), Some(Direction::North));
+        assert_eq!(Direction::towards(origin, Point::new(5, 5)), Some(Direction::SouthEast));
+        assert_eq!(Direction::towards(origin, Point::new(3, 4)), None);
+        assert_eq!(Direction::towards(origin, origin), None);
+        assert_eq!(Direction::towards(Point::new(4_usize, 1), Point::new(0, 1)), Some(Direction::West));
+    }
+
+    #[test]
+    fn point_neighbours_and_distances() {
+        let p = Point::new(0, 0);
+
+        assert_eq!(p.neighbours4(), [Point::new(0, -1), Point::new(1, 0), Point::new(0, 1), Point::new(-1, 0)]);
+        assert_eq!(p.neighbours8().len(), 8);
+        assert!(p.neighbours8().iter().all(|n| n.chebyshev(p) == 1));
+
+        assert_eq!(Point::new(1, 5).manhattan(Point::new(4, 1)), 7);
+        assert_eq!(Point::new(1, 5).chebyshev(Point::new(4, 1)), 4);
+        assert_eq!(Point::<usize>::new(4, 1).manhattan(Point::new(1, 5)), 7);
+    }
+
+    #[test]
+    fn point_conversions() {
+        assert_eq!(Point::<usize>::try_from(Point::new(3, 4)), Ok(Point::new(3, 4)));
+        assert_eq!(Point::<i32>::try_from(Point::new(3_usize, 4)), Ok(Point::new(3, 4)));
+
+        let error = Point::<usize>::try_from(Point::new(3_i32, -1)).unwrap_err();
+        assert_eq!(error, PointOutOfRange { x: 3, y: -1 });
+        assert_eq!(error.to_string(), "Point (x:3,y:-1) is out of range");
+        assert!(Point::<i32>::try_from(Point::new(usize::MAX, 0)).is_err());
+    }
+
+    #[test]
+    fn point_offset_is_checked() {
+        let corner = Point::new(0_usize, 0);
+
+        assert_eq!(corner.offset(Direction::East), Some(Point::new(1, 0)));
+        assert_eq!(corner.offset(Direction::SouthEast), Some(Point::new(1, 1)));
+        assert_eq!(corner.offset(Direction::North), None);
+        assert_eq!(corner.offset(Direction::West), None);
+        assert_eq!(Point::new(5_usize, 5).checked_add(Point::new(-5, 2)), Some(Point::new(0, 7)));
+    }
+
+    #[test]
+    fn point_arithmetic() {
+        assert_eq!(-Point::new(1, -2), Point::new(-1, 2));
+        assert_eq!(Point::new(1, -2) * 3, Point::new(3, -6));
+        assert_eq!(Direction::West.to_point() * 4, Point::new(-4, 0));
+        assert_eq!(Point::new(7, -9) / 2, Point::new(3, -4));
+        assert_eq!(Point::new(7, 9) % 4, Point::new(3, 1));
+        assert_eq!(Point::new(2, 3).dot(Point::new(4, -1)), 5);
+        assert_eq!(Point::<i64>::default(), Point::new(0, 0));
+    }
+
+    #[test]
+    fn point_n_arithmetic() {
+        let a = Point3::new([1, 2, 3]);
+        let b = Point3::new([4, -5, 6]);
+
+        assert_eq!(a + b, Point3::new([5, -3, 9]));
+        assert_eq!(a - b, Point3::new([-3, 7, -3]));
+        assert_eq!(-a, Point3::new([-1, -2, -3]));
+        assert_eq!(b * 2, Point3::new([8, -10, 12]));
+        assert_eq!(b / 2, Point3::new([2, -2, 3]));
+        assert_eq!(b % 4, Point3::new([0, -1, 2]));
+        assert_eq!(a.dot(b), 12);
+        assert_eq!(a.manhattan(b), 13);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!((*b.x(), *b.y(), *b.z()), (4, -5, 6));
+        assert_eq!(Point3::<i32>::default(), Point3::new([0, 0, 0]));
+        assert_eq!(a.to_string(), "(1,2,3)");
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        c[2] = 0;
+        assert_eq!(c, Point3::new([4, -5, 0]));
+
+        let flat: PointN<i32, 2> = Point::new(3, 4).into();
+        assert_eq!(Point::from(flat + flat), Point::new(6, 8));
+    }
+
+    #[test]
+    fn rect_size() {
+        let rect = Rect::new(2, 1, 6, 4);
+
+        assert_eq!((rect.width(), rect.height(), rect.area()), (4, 3, 12));
+        assert_eq!(Rect::new(5, 0, 2, 3).area(), 0);
+        assert!(Rect::new(5, 0, 2, 3).is_empty());
+        assert_eq!(Rect::<usize>::from_size(3, 2).area(), 6);
+        assert!(rect.is_inside(Point::new(2, 1)));
+        assert!(!rect.is_inside(Point::new(6, 1)));
+    }
+
+    #[test]
+    fn rect_points() {
+        let points: Vec<Point<usize>> = Rect::new(1, 1, 3, 3).points().collect();
+
+        assert_eq!(points, vec![Point::new(1, 1), Point::new(2, 1), Point::new(1, 2), Point::new(2, 2)]);
+        assert_eq!(Rect::new(0, 0, 0, 5).points().count(), 0);
+        assert_eq!(Rect::new(-2, -2, 3, 1).points().count(), 15);
+    }
+
+    #[test]
+    fn rect_set_operations() {
+        let a = Rect::new(0, 0, 4, 4);
+        let b = Rect::new(2, 3, 6, 8);
+
+        assert_eq!(a.intersection(&b), Some(Rect::new(2, 3, 4, 4)));
+        assert_eq!(a.intersection(&Rect::new(4, 0, 6, 4)), None);
+        assert_eq!(a.union(&b), Rect::new(0, 0, 6, 8));
+    }
+
+    #[test]
+    fn rect_quadrants() {
+        //Odd sides leave out the middle line
+        let [top_left, top_right, bottom_left, bottom_right] = Rect::from_size(11, 7).quadrants();
+        assert_eq!(top_left, Rect::new(0, 0, 5, 3));
+        assert_eq!(top_right, Rect::new(6, 0, 11, 3));
+        assert_eq!(bottom_left, Rect::new(0, 4, 5, 7));
+        assert_eq!(bottom_right, Rect::new(6, 4, 11, 7));
+
+        let even = Rect::new(2, 2, 6, 6);
+        assert_eq!(even.quadrants().iter().map(Rect::area).sum::<i32>(), even.area());
+    }
+
+    #[test]
+    fn rect_wrap() {
+        let world = Rect::from_size(11, 7);
+
+        assert_eq!(world.wrap(Point::new(3, 4)), Point::new(3, 4));
+        assert_eq!(world.wrap(Point::new(-1, 7)), Point::new(10, 0));
+        assert_eq!(world.wrap(Point::new(-23, -14)), Point::new(10, 0));
+        assert_eq!(world.wrap(Point::new(25, 15)), Point::new(3, 1));
+
+        let offset = Rect::new(5_usize, 5, 8, 8);
+        assert_eq!(offset.wrap(Point::new(1, 9)), Point::new(7, 6));
+
+        assert_eq!(Rect::from_size(0, 7).wrap(Point::new(-1, 9)), Point::new(-1, 9));
+        assert_eq!(Rect::new(4_usize, 4, 6, 4).wrap(Point::new(1, 9)), Point::new(1, 9));
+    }
+
+    #[test]
+    fn grid_parse_and_render() {
+        let grid = Grid::parse(MAP, Some).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (5, 3));
+        assert_eq!(grid.to_string(), MAP);
+
+        let heights = Grid::parse("01\n23", |c| c.to_digit(10)).unwrap();
+        assert_eq!(heights[Point::new(1, 1)], 3);
+    }
+
+    #[test]
+    fn grid_parse_errors() {
+        assert_eq!(Grid::parse("01\n2x", |c| c.to_digit(10)).unwrap_err().to_string(), "Unexpected 'x' at line 2, column 2");
+        assert_eq!(Grid::parse("012\n34", |c| c.to_digit(10)).unwrap_err().to_string(), "Line 2 is 2 wide, expected 3");
+    }
+
+    #[test]
+    fn grid_get_is_bounds_checked() {
+        let mut grid = Grid::parse(MAP, Some).unwrap();
+
+        assert_eq!(grid.get(Point::new(1, 1)), Some(&'S'));
+        assert_eq!(grid.get(Point::new(-1, 1)), None);
+        assert_eq!(grid.get(Point::new(5, 1)), None);
+        assert_eq!(grid.get(Point::new(0, 3)), None);
+        assert!(!grid.contains(Point::new(0, -1)));
+
+        *grid.get_mut(Point::new(2, 1)).unwrap() = 'O';
+        assert_eq!(grid.row(1), Some(&['#', 'S', 'O', 'E', '#'][..]));
+        assert!(grid.get_mut(Point::new(2, 7)).is_none());
+    }
+
+    #[test]
+    fn grid_find_cells() {
+        let grid = Grid::parse(MAP, Some).unwrap();
+
+        assert_eq!(grid.find(|&c| c == 'S'), Some(Point::new(1, 1)));
+        assert_eq!(grid.find(|&c| c == 'x'), None);
+        assert_eq!(grid.positions(|&c| c != '#').collect::<Vec<_>>(), vec![Point::new(1, 1), Point::new(2, 1), Point::new(3, 1)]);
+    }
+
+    #[test]
+    fn grid_rows_and_columns() {
+        let grid = Grid::parse("abc\ndef", Some).unwrap();
+
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, vec!["abc", "def"]);
+
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+        assert_eq!(grid.column(2).unwrap().collect::<String>(), "cf");
+        assert!(grid.column(3).is_none());
+        assert!(grid.row(2).is_none());
+    }
+}
@@ -0,0 +1,393 @@
+//Graph searches that work on any node type
+//
+//The graph is never built up front, instead a successor function gives the neighbours of a node
+//(with the cost of moving to each for the weighted searches), so a node can be a map position,
+//a (position, facing) pair or anything else that is Eq + Hash + Clone.
+//
+//  let path = dijkstra(start, |&p| moves_from(p), |&p| p == goal);
+
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap, HashSet, VecDeque}, hash::Hash, ops::Add};
+
+//Costs start at Default, which is zero for all the integer types
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+
+impl<C> Cost for C where C: Copy + Ord + Default + Add<Output = C> {}
+
+//A route found by one of the searches
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    //Every node visited, starting with the start node and ending at the goal
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+impl<N, C> Path<N, C> {
+    pub fn goal(&self) -> &N {
+        self.nodes.last().expect("A path always contains its start node")
+    }
+}
+
+//Nodes seen by a search, stored once and referred to by index
+struct Visited<N> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    parents: Vec<Option<usize>>,
+}
+
+impl<N: Eq + Hash + Clone> Visited<N> {
+    fn new() -> Visited<N> {
+        Visited { nodes: Vec::new(), indices: HashMap::new(), parents: Vec::new() }
+    }
+
+    //Index of the node, and whether it was seen for the first time
+    fn insert(&mut self, node: N, parent: Option<usize>) -> (usize, bool) {
+        if let Some(&index) = self.indices.get(&node) {
+            return (index, false)
+        }
+
+        let index = self.nodes.len();
+        self.indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.parents.push(parent);
+
+        (index, true)
+    }
+
+    //Follow the parents back to the start
+    fn path_to(&self, mut index: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[index].clone()];
+
+        while let Some(parent) = self.parents[index] {
+            path.push(self.nodes[parent].clone());
+            index = parent;
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+//Breadth first search for the route with the fewest steps, the cost is the number of steps
+pub fn bfs<N, S, I>(start: N, mut successors: S, mut is_goal: impl FnMut(&N) -> bool) -> Option<Path<N, usize>>
+    where N: Eq + Hash + Clone, S: FnMut(&N) -> I, I: IntoIterator<Item = N> {
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::from([visited.insert(start, None).0]);
+
+    while let Some(index) = queue.pop_front() {
+        let node = visited.nodes[index].clone();
+
+        if is_goal(&node) {
+            let nodes = visited.path_to(index);
+            return Some(Path { cost: nodes.len() - 1, nodes })
+        }
+
+        for next in successors(&node) {
+            //Marking nodes as seen when they're queued keeps each one in the queue once
+            if let (next_index, true) = visited.insert(next, Some(index)) {
+                queue.push_back(next_index);
+            }
+        }
+    }
+
+    None
+}
+
+//Every node that can be reached from the start, including the start
+pub fn reachable<N, S, I>(start: N, mut successors: S) -> HashSet<N>
+    where N: Eq + Hash + Clone, S: FnMut(&N) -> I, I: IntoIterator<Item = N> {
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    seen
+}
+
+//Whether any goal can be reached, without keeping track of the route
+pub fn can_reach<N, S, I>(start: N, mut successors: S, mut is_goal: impl FnMut(&N) -> bool) -> bool
+    where N: Eq + Hash + Clone, S: FnMut(&N) -> I, I: IntoIterator<Item = N> {
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return true
+        }
+
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    false
+}
+
+//Cheapest route to a goal, costs must not be negative
+pub fn dijkstra<N, C, S, I>(start: N, successors: S, is_goal: impl FnMut(&N) -> bool) -> Option<Path<N, C>>
+    where N: Eq + Hash + Clone, C: Cost, S: FnMut(&N) -> I, I: IntoIterator<Item = (N, C)> {
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+//Cheapest route to a goal, guided by a heuristic that must be consistent (monotone): never more than the
+//cost of a step plus the heuristic from where it leads, and 0 at goals. Nodes aren't revisited once
+//they're taken off the heap, which an admissible but inconsistent heuristic would need to find the cheapest route
+pub fn astar<N, C, S, I>(start: N, mut successors: S, mut heuristic: impl FnMut(&N) -> C, mut is_goal: impl FnMut(&N) -> bool) -> Option<Path<N, C>>
+    where N: Eq + Hash + Clone, C: Cost, S: FnMut(&N) -> I, I: IntoIterator<Item = (N, C)> {
+    let mut visited = Visited::new();
+    let mut costs: Vec<C> = vec![C::default()];
+    let mut done: Vec<bool> = vec![false];
+
+    //Min-heap of (estimated total cost, cost so far, node index)
+    let mut heap = BinaryHeap::new();
+    let (start_index, _) = visited.insert(start, None);
+    heap.push(Reverse((heuristic(&visited.nodes[start_index]), C::default(), start_index)));
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        //Skip stale entries, a cheaper route here has already been handled
+        if done[index] || cost > costs[index] {
+            continue;
+        }
+        done[index] = true;
+
+        let node = visited.nodes[index].clone();
+
+        if is_goal(&node) {
+            return Some(Path { nodes: visited.path_to(index), cost })
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            let (next_index, is_new) = visited.insert(next, Some(index));
+
+            if is_new {
+                costs.push(next_cost);
+                done.push(false);
+            } else if done[next_index] || next_cost >= costs[next_index] {
+                continue;
+            } else {
+                //Relaxation, we have now found a better way
+                costs[next_index] = next_cost;
+                visited.parents[next_index] = Some(index);
+            }
+
+            heap.push(Reverse((next_cost + heuristic(&visited.nodes[next_index]), next_cost, next_index)));
+        }
+    }
+
+    None
+}
+
+//Every cheapest route to the goal at once, as a graph of the optimal predecessors of each node
+#[derive(Clone, Debug)]
+pub struct AllPaths<N, C> {
+    pub cost: C,
+    //The goal nodes reached at the cheapest cost
+    pub goals: Vec<N>,
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N: Eq + Hash + Clone, C> AllPaths<N, C> {
+    //Every (from, to) step that lies on at least one cheapest route, found by walking back from the goals
+    pub fn steps(&self) -> HashSet<(N, N)> {
+        let mut steps = HashSet::new();
+        let mut seen: HashSet<&N> = self.goals.iter().collect();
+        let mut stack: Vec<&N> = self.goals.iter().collect();
+
+        while let Some(node) = stack.pop() {
+            for parent in self.predecessors.get(node).into_iter().flatten() {
+                steps.insert((parent.clone(), node.clone()));
+                if seen.insert(parent) {
+                    stack.push(parent);
+                }
+            }
+        }
+
+        steps
+    }
+
+    //Every node that lies on at least one cheapest route, including the start and goals
+    pub fn nodes(&self) -> HashSet<N> {
+        self.steps()
+            .into_iter()
+            .flat_map(|(from, to)| [from, to])
+            .chain(self.goals.iter().cloned())
+            .collect()
+    }
+}
+
+//Like dijkstra, but keeps every predecessor that reaches a node at its cheapest cost rather than just the first
+pub fn dijkstra_all<N, C, S, I>(start: N, mut successors: S, mut is_goal: impl FnMut(&N) -> bool) -> Option<AllPaths<N, C>>
+    where N: Eq + Hash + Clone, C: Cost, S: FnMut(&N) -> I, I: IntoIterator<Item = (N, C)> {
+    let mut costs: HashMap<N, C> = HashMap::from([(start.clone(), C::default())]);
+    let mut predecessors: HashMap<N, Vec<N>> = HashMap::new();
+    let mut done: HashSet<N> = HashSet::new();
+    let mut goals: Vec<N> = Vec::new();
+    let mut best: Option<C> = None;
+
+    //The heap refers to nodes by index so they only need to be Eq + Hash
+    let mut nodes: Vec<N> = vec![start];
+    let mut heap = BinaryHeap::from([Reverse((C::default(), 0))]);
+
+    while let Some(Reverse((cost, index))) = heap.pop() {
+        //Once past the goal's cost every cheapest route has been found
+        if best.is_some_and(|best| cost > best) {
+            break;
+        }
+
+        let node = nodes[index].clone();
+        if cost > costs[&node] || !done.insert(node.clone()) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            best = Some(cost);
+            goals.push(node);
+            continue;
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+
+            match costs.get(&next) {
+                Some(&known) if next_cost > known => continue,
+                Some(&known) if next_cost == known => {
+                    //A tie, another way to get here just as cheaply
+                    predecessors.entry(next).or_default().push(node.clone());
+                    continue;
+                },
+                _ => (),
+            }
+
+            costs.insert(next.clone(), next_cost);
+            predecessors.insert(next.clone(), vec![node.clone()]);
+            heap.push(Reverse((next_cost, nodes.len())));
+            nodes.push(next);
+        }
+    }
+
+    best.map(|cost| AllPaths { cost, goals, predecessors })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::misc_types::{Grid, Point};
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn open_neighbours(grid: &Grid<char>, p: Point<i32>) -> Vec<Point<i32>> {
+        p.neighbours4().into_iter().filter(|&n| grid.get(n).is_some_and(|&c| c != '#')).collect()
+    }
+
+    #[test]
+    fn bfs_finds_the_fewest_steps() {
+        let grid = Grid::parse(MAZE, Some).unwrap();
+        let start = grid.find(|&c| c == 'S').unwrap();
+        let end = grid.find(|&c| c == 'E').unwrap();
+
+        let path = bfs(start, |&p| open_neighbours(&grid, p), |&p| p == end).unwrap();
+
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.nodes.len(), 16);
+        assert_eq!((path.nodes[0], *path.goal()), (start, end));
+        assert!(path.nodes.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        assert!(bfs(start, |&p| open_neighbours(&grid, p), |&p| p == Point::new(3, 0)).is_none());
+    }
+
+    #[test]
+    fn reachability() {
+        let grid = Grid::parse("S.#.\n..#E", Some).unwrap();
+        let start = Point::new(0, 0);
+
+        assert_eq!(reachable(start, |&p| open_neighbours(&grid, p)).len(), 4);
+        assert!(can_reach(start, |&p| open_neighbours(&grid, p), |&p| p == Point::new(1, 1)));
+        assert!(!can_reach(start, |&p| open_neighbours(&grid, p), |&p| p == Point::new(3, 1)));
+    }
+
+    //Weighted graph where the direct edges are more expensive than the long way round
+    fn weighted(node: &u8) -> Vec<(u8, u32)> {
+        match node {
+            0 => vec![(1, 7), (2, 1), (3, 20)],
+            1 => vec![(3, 1)],
+            2 => vec![(1, 2), (4, 10)],
+            3 => vec![(4, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn dijkstra_finds_the_cheapest_path() {
+        let path = dijkstra(0, weighted, |&n| n == 4).unwrap();
+
+        assert_eq!(path, Path { nodes: vec![0, 2, 1, 3, 4], cost: 5 });
+        assert_eq!(dijkstra(0, weighted, |&n| n == 0).unwrap(), Path { nodes: vec![0], cost: 0 });
+        assert!(dijkstra(4, weighted, |&n| n == 0).is_none());
+    }
+
+    #[test]
+    fn dijkstra_all_keeps_tied_routes() {
+        //Two routes of cost 4 from 0 to 5 and a dearer one that should be left out
+        let graph = |node: &u8| match node {
+            0 => vec![(1, 1), (2, 1), (3, 1)],
+            1 => vec![(4, 2)],
+            2 => vec![(4, 2)],
+            3 => vec![(4, 5)],
+            4 => vec![(5, 1)],
+            _ => vec![],
+        };
+
+        let all = dijkstra_all(0_u8, graph, |&n| n == 5).unwrap();
+
+        assert_eq!(all.cost, 4);
+        assert_eq!(all.goals, vec![5]);
+        assert_eq!(all.steps(), HashSet::from([(0, 1), (0, 2), (1, 4), (2, 4), (4, 5)]));
+        assert_eq!(all.nodes(), HashSet::from([0, 1, 2, 4, 5]));
+        assert!(dijkstra_all(5_u8, graph, |&n| n == 0).is_none());
+    }
+
+    #[test]
+    fn dijkstra_all_collects_every_cheapest_goal() {
+        //Goals are (position, arrived from) pairs, both sides reach 2 at the same cost
+        let graph = |&(pos, _): &(i32, i32)| vec![((pos + 1, pos), 1), ((pos - 1, pos), 1)];
+
+        let all = dijkstra_all((0, 0), graph, |&(pos, _)| pos.abs() == 2).unwrap();
+
+        assert_eq!(all.cost, 2);
+        assert_eq!(all.goals.len(), 2);
+        assert_eq!(all.nodes().len(), 5);
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let grid = Grid::parse(MAZE, Some).unwrap();
+        let start = grid.find(|&c| c == 'S').unwrap();
+        let end = grid.find(|&c| c == 'E').unwrap();
+
+        //Moving down costs more, so the cheapest route isn't always the shortest
+        let moves = |&p: &Point<i32>| open_neighbours(&grid, p)
+            .into_iter()
+            .map(move |n| (n, if n.y > p.y { 3 } else { 1 }))
+            .collect::<Vec<_>>();
+
+        let expected = dijkstra(start, moves, |&p| p == end).unwrap();
+        let found = astar(start, moves, |p| p.manhattan(end), |&p| p == end).unwrap();
+
+        assert_eq!(found.cost, expected.cost);
+        assert_eq!(*found.goal(), end);
+    }
+}
@@ -0,0 +1,72 @@
+//Checks every day against the answers recorded in answers.toml,
+//so a refactor of the shared helpers can't quietly change a result.
+
+use crate::answers::parse_answers;
+use crate::input_utils::read_input;
+use crate::solver::{self, Solver};
+
+const RECORDED: &str = include_str!("../answers.toml");
+
+fn check_part(day: u8, part: u8) {
+    let answers = parse_answers(RECORDED).unwrap();
+    let expected = answers.get(&(day, part)).unwrap_or_else(|| panic!("No answer recorded for day {day} part {part}"));
+    let input = read_input(day).unwrap();
+
+    assert_eq!(expected, &solver::find(day, part).unwrap().solve(&input).unwrap(), "Day {day} part {part}");
+}
+
+fn check_day(day: u8) {
+    let answers = parse_answers(RECORDED).unwrap();
+    let parts: Vec<u8> = (1..=2).filter(|&part| answers.contains_key(&(day, part))).collect();
+
+    assert!(!parts.is_empty(), "No answers recorded for day {day}");
+
+    for part in parts {
+        check_part(day, part);
+    }
+}
+
+macro_rules! recorded_answer_tests {
+    ($($(#[$attr:meta])* $name:ident => $day:literal),* $(,)?) => {
+        $(
+            #[test]
+            $(#[$attr])*
+            fn $name() {
+                check_day($day);
+            }
+        )*
+    };
+}
+
+recorded_answer_tests!(
+    day_1 => 1,
+    day_2 => 2,
+    day_3 => 3,
+    day_4 => 4,
+    day_5 => 5,
+    day_6 => 6,
+    day_7 => 7,
+    day_8 => 8,
+    day_9 => 9,
+    day_10 => 10,
+    day_11 => 11,
+    day_12 => 12,
+    day_13 => 13,
+    day_14 => 14,
+    day_15 => 15,
+    day_16 => 16,
+    day_18 => 18,
+    day_19 => 19,
+    day_20 => 20,
+);
+
+#[test]
+fn day_17_part1() {
+    check_part(17, 1);
+}
+
+#[test]
+#[ignore = "part 2 is a brute force search that takes minutes, run with --ignored"]
+fn day_17_part2() {
+    check_part(17, 2);
+}
@@ -0,0 +1,108 @@
+//Machine-readable results for the runner's --format json mode
+//
+//Each day/part produces one record, printed as a single line of JSON:
+//  {"day":18,"part":2,"answer":"24,32","elapsed_ms":12.5,"status":"solved","error":null}
+
+use std::{fmt::Display, time::Duration};
+
+use crate::json::Json;
+use crate::solver::Answer;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    Error,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Solved => write!(f, "solved"),
+            Status::Unsolved => write!(f, "unsolved"),
+            Status::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunRecord {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<Answer>,
+    pub elapsed: Option<Duration>,
+    pub error: Option<String>,
+}
+
+impl RunRecord {
+    pub fn solved(day: u8, part: u8, answer: Answer, elapsed: Duration) -> RunRecord {
+        RunRecord { day, part, answer: Some(answer), elapsed: Some(elapsed), error: None }
+    }
+
+    pub fn unsolved(day: u8, part: u8) -> RunRecord {
+        RunRecord { day, part, answer: None, elapsed: None, error: None }
+    }
+
+    pub fn failed(day: u8, part: u8, error: &anyhow::Error, elapsed: Option<Duration>) -> RunRecord {
+        RunRecord { day, part, answer: None, elapsed, error: Some(format!("{error:#}")) }
+    }
+
+    pub fn status(&self) -> Status {
+        match (&self.answer, &self.error) {
+            (_, Some(_)) => Status::Error,
+            (Some(_), None) => Status::Solved,
+            (None, None) => Status::Unsolved,
+        }
+    }
+
+    pub fn to_json(&self) -> Json {
+        //Numeric answers stay numbers so consumers don't have to parse them
+        let answer = match &self.answer {
+            Some(Answer::Int(value)) => Json::Int(*value as i128),
+            Some(Answer::BigInt(value)) => Json::Int(*value),
+            Some(Answer::Text(value)) => Json::Str(value.clone()),
+            None => Json::Null,
+        };
+
+        Json::object([
+            ("day", Json::Int(self.day as i128)),
+            ("part", Json::Int(self.part as i128)),
+            ("answer", answer),
+            //Milliseconds to the nearest microsecond
+            ("elapsed_ms", self.elapsed.map_or(Json::Null, |e| Json::Float((e.as_secs_f64() * 1e6).round() / 1e3))),
+            ("status", Json::Str(self.status().to_string())),
+            ("error", self.error.clone().map_or(Json::Null, Json::Str)),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_are_typed() {
+        let solved = RunRecord::solved(18, 2, Answer::from("24,32"), Duration::from_micros(1500));
+        assert_eq!(solved.status(), Status::Solved);
+        assert_eq!(
+            solved.to_json().to_string(),
+            r#"{"day":18,"part":2,"answer":"24,32","elapsed_ms":1.5,"status":"solved","error":null}"#
+        );
+
+        let numeric = RunRecord::solved(13, 2, Answer::Int(101406661266314), Duration::ZERO);
+        assert_eq!(numeric.to_json().get("answer"), Some(&Json::Int(101406661266314)));
+    }
+
+    #[test]
+    fn unsolved_and_failed_records() {
+        assert_eq!(
+            RunRecord::unsolved(21, 1).to_json().to_string(),
+            r#"{"day":21,"part":1,"answer":null,"elapsed_ms":null,"status":"unsolved","error":null}"#
+        );
+
+        let error = anyhow::anyhow!("Failed to read input").context("Day 5");
+        let failed = RunRecord::failed(5, 1, &error, None);
+        assert_eq!(failed.status(), Status::Error);
+        assert_eq!(failed.to_json().get("error"), Some(&Json::Str("Day 5: Failed to read input".into())));
+    }
+}
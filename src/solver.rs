@@ -0,0 +1,275 @@
+//A common shape for every day's solutions, plus a registry of them all
+//so runners, tests and benchmarks can enumerate the puzzles without a match arm per day.
+
+use std::fmt::Display;
+
+use crate::*;
+
+//The answer to a puzzle part, days return a mix of integer widths and text
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{value}"),
+            Answer::BigInt(value) => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Int(value as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Int(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        match i64::try_from(value) {
+            Ok(value) => Answer::Int(value),
+            Err(_) => Answer::BigInt(value as i128),
+        }
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        match i64::try_from(value) {
+            Ok(value) => Answer::Int(value),
+            Err(_) => Answer::BigInt(value),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+pub trait Solver {
+    fn day(&self) -> u8;
+    fn part(&self) -> u8;
+    fn solve(&self, input: &str) -> anyhow::Result<Answer>;
+
+    //Parts that are still stubs report false so they can be skipped
+    fn is_solved(&self) -> bool {
+        true
+    }
+
+    //Parts that take minutes report true so benchmarks only run them when asked to
+    fn is_slow(&self) -> bool {
+        false
+    }
+}
+
+//A registry entry wrapping one day's solve_partN function
+pub struct Solution {
+    pub day: u8,
+    pub part: u8,
+    pub solved: bool,
+    pub slow: bool,
+    solve_fn: fn(&str) -> anyhow::Result<Answer>,
+}
+
+impl Solver for Solution {
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn part(&self) -> u8 {
+        self.part
+    }
+
+    fn solve(&self, input: &str) -> anyhow::Result<Answer> {
+        (self.solve_fn)(input)
+    }
+
+    fn is_solved(&self) -> bool {
+        self.solved
+    }
+
+    fn is_slow(&self) -> bool {
+        self.slow
+    }
+}
+
+macro_rules! solution {
+    ($day:literal, $part:literal, $solve:path) => {
+        solution!($day, $part, $solve, true, false)
+    };
+    ($day:literal, $part:literal, $solve:path, unsolved) => {
+        solution!($day, $part, $solve, false, false)
+    };
+    ($day:literal, $part:literal, $solve:path, slow) => {
+        solution!($day, $part, $solve, true, true)
+    };
+    ($day:literal, $part:literal, $solve:path, $solved:literal, $slow:literal) => {
+        Solution {
+            day: $day,
+            part: $part,
+            solved: $solved,
+            slow: $slow,
+            solve_fn: |input| Ok($solve(input)?.into()),
+        }
+    };
+}
+
+pub static SOLUTIONS: &[Solution] = &[
+    solution!(1, 1, day_1::solve_part1),
+    solution!(1, 2, day_1::solve_part2),
+    solution!(2, 1, day_2::solve_part1),
+    solution!(2, 2, day_2::solve_part2),
+    solution!(3, 1, day_3::solve_part1),
+    solution!(3, 2, day_3::solve_part2),
+    solution!(4, 1, day_4::solve_part1),
+    solution!(4, 2, day_4::solve_part2),
+    solution!(5, 1, day_5::solve_part1),
+    solution!(5, 2, day_5::solve_part2),
+    solution!(6, 1, day_6::solve_part1),
+    solution!(6, 2, day_6::solve_part2),
+    solution!(7, 1, day_7::solve_part1),
+    solution!(7, 2, day_7::solve_part2),
+    solution!(8, 1, day_8::solve_part1),
+    solution!(8, 2, day_8::solve_part2),
+    solution!(9, 1, day_9::solve_part1),
+    solution!(9, 2, day_9::solve_part2),
+    solution!(10, 1, day_10::solve_part1),
+    solution!(10, 2, day_10::solve_part2),
+    solution!(11, 1, day_11::solve_part1),
+    solution!(11, 2, day_11::solve_part2),
+    solution!(12, 1, day_12::solve_part1),
+    solution!(12, 2, day_12::solve_part2),
+    solution!(13, 1, day_13::solve_part1),
+    solution!(13, 2, day_13::solve_part2),
+    solution!(14, 1, day_14::solve_part1),
+    solution!(14, 2, day_14::solve_part2),
+    solution!(15, 1, day_15::solve_part1),
+    solution!(15, 2, day_15::solve_part2),
+    solution!(16, 1, day_16::solve_part1),
+    solution!(16, 2, day_16::solve_part2),
+    solution!(17, 1, day_17::solve_part1),
+    solution!(17, 2, day_17::solve_part2, slow),
+    solution!(18, 1, day_18::solve_part1),
+    solution!(18, 2, day_18::solve_part2),
+    solution!(19, 1, day_19::solve_part1),
+    solution!(19, 2, day_19::solve_part2),
+    solution!(20, 1, day_20::solve_part1),
+    solution!(20, 2, day_20::solve_part2, unsolved),
+    solution!(21, 1, day_21::solve_part1, unsolved),
+    solution!(21, 2, day_21::solve_part2, unsolved),
+    solution!(22, 1, day_22::solve_part1, unsolved),
+    solution!(22, 2, day_22::solve_part2, unsolved),
+    solution!(23, 1, day_23::solve_part1, unsolved),
+    solution!(23, 2, day_23::solve_part2, unsolved),
+    solution!(24, 1, day_24::solve_part1, unsolved),
+    solution!(24, 2, day_24::solve_part2, unsolved),
+    solution!(25, 1, day_25::solve_part1, unsolved),
+    solution!(25, 2, day_25::solve_part2, unsolved),
+];
+
+//A day's input parsing step, registered for the days that parse separately from solving
+//so benchmarks can time it on its own. Other days parse inside each part's solve function
+pub struct Parser {
+    pub day: u8,
+    parse_fn: fn(&str) -> anyhow::Result<()>,
+}
+
+impl Parser {
+    pub fn parse(&self, input: &str) -> anyhow::Result<()> {
+        (self.parse_fn)(input)
+    }
+}
+
+macro_rules! parser {
+    ($day:literal, $parse:path) => {
+        Parser {
+            day: $day,
+            parse_fn: |input| $parse(input).map(|parsed| { std::hint::black_box(parsed); }),
+        }
+    };
+}
+
+pub static PARSERS: &[Parser] = &[
+    parser!(1, day_1::parse_sorted_lists),
+    parser!(2, day_2::parse_reports),
+    parser!(5, day_5::parse_manual),
+    parser!(6, day_6::parse_lab),
+    parser!(7, day_7::parse_equations),
+    parser!(8, day_8::parse_antennas),
+    parser!(9, day_9::parse_disk_map),
+    parser!(10, day_10::parse_map),
+    parser!(11, day_11::parse_stones),
+    parser!(12, day_12::parse_map),
+    //Timed with the wider numbers part 2 parses into
+    parser!(13, day_13::parse_machines::<i128>),
+    parser!(14, day_14::parse_robots),
+    parser!(15, day_15::parse_warehouse),
+    parser!(16, day_16::parse_maze),
+    parser!(17, day_17::parse_program),
+    parser!(18, day_18::parse_bytes),
+    parser!(19, day_19::parse_towels),
+    parser!(20, day_20::parse_racetrack),
+];
+
+pub fn find_parser(day: u8) -> Option<&'static Parser> {
+    PARSERS.iter().find(|p| p.day == day)
+}
+
+pub fn find(day: u8, part: u8) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|s| s.day == day && s.part == part)
+}
+
+//All registered solutions for the given days, optionally limited to one part
+pub fn select(days: &[u8], part: Option<u8>) -> impl Iterator<Item = &'static Solution> + '_ {
+    SOLUTIONS.iter()
+        .filter(move |s| days.contains(&s.day) && part.is_none_or(|p| p == s.part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_covers_every_day_and_part() {
+        for day in 1..=25 {
+            for part in 1..=2 {
+                let solution = find(day, part).unwrap_or_else(|| panic!("Day {day} part {part} is not registered"));
+                assert_eq!((solution.day(), solution.part()), (day, part));
+            }
+        }
+        assert_eq!(SOLUTIONS.len(), 50);
+    }
+
+    #[test]
+    fn select_filters_by_day_and_part() {
+        let selected: Vec<(u8, u8)> = select(&[3, 5], Some(2)).map(|s| (s.day, s.part)).collect();
+        assert_eq!(selected, vec![(3, 2), (5, 2)]);
+    }
+
+    #[test]
+    fn answers_convert_to_the_narrowest_type() {
+        assert_eq!(Answer::from(42_usize), Answer::Int(42));
+        assert_eq!(Answer::from(42_i128), Answer::Int(42));
+        assert_eq!(Answer::from(i128::MAX), Answer::BigInt(i128::MAX));
+        assert_eq!(Answer::from("6,1").to_string(), "6,1");
+    }
+}